@@ -1,39 +1,265 @@
-use serde::Deserialize;
+use crate::environment::BaseUrlError;
+use crate::models::types::afm::Afm;
+use crate::transport::{TransportError, TransportErrorKind};
+use crate::validation::ValidationErrors;
+use reqwest::header::InvalidHeaderValue;
+use reqwest::StatusCode;
+use serde_json::Value;
 use std::fmt::{Display, Formatter};
+use std::time::Duration;
 
-#[derive(Deserialize, Debug)]
+/// An error response returned by the Ergani API
+/// * - `status` - The HTTP status of the response
+/// * - `message` - The error message reported by the Ergani API, or the raw body if it carried none
+/// * - `body` - The raw body of the response
+#[derive(Debug, Clone)]
 pub struct ErganiError {
+    pub(crate) status: StatusCode,
     pub(crate) message: String,
+    pub(crate) body: String,
 }
 
 impl ErganiError {
+    /// Creates an [ErganiError] from an error response, whether or not its body is JSON
+    ///
+    /// # Arguments
+    /// * - `status` - The HTTP status of the response
+    /// * - `body` - The raw body of the response
+    pub(crate) fn from_response(status: StatusCode, body: String) -> Self {
+        let message = serde_json::from_str::<Value>(&body)
+            .ok()
+            .and_then(|value| {
+                value
+                    .get("message")
+                    .or_else(|| value.get("Message"))
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            })
+            .unwrap_or_else(|| body.clone());
+
+        ErganiError {
+            status,
+            message,
+            body,
+        }
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
     pub fn message(&self) -> &str {
         self.message.trim()
     }
+
+    pub fn body(&self) -> &str {
+        &self.body
+    }
 }
 
+#[non_exhaustive]
 #[derive(thiserror::Error, Debug)]
-pub enum APIError {
+pub enum ErganiClientError {
+    /// Raised when the HTTP client could not be configured
+    Configuration(#[source] reqwest::Error),
+    /// Raised when the Ergani API could not be reached
+    Network(#[source] TransportError),
+    /// Raised when a request to the Ergani API timed out
+    Timeout(#[source] TransportError),
+    /// Raised when the access token has characters that cannot be sent in an `Authorization` header
+    InvalidAccessToken(#[source] InvalidHeaderValue),
+    /// Raised when a request payload could not be serialized
+    Serialization(#[source] serde_json::Error),
+    /// Raised when a response of the Ergani API could not be deserialized
+    /// * - `source` - The underlying deserialization error
+    /// * - `body` - The raw body of the response
+    Deserialization {
+        #[source]
+        source: serde_json::Error,
+        body: String,
+    },
+    /// Raised when the data is rejected before it is sent to the Ergani API
+    Validation(String),
+    /// Raised when documents are refused before they are submitted, with every issue found in them
+    InvalidDocuments(ValidationErrors),
+    /// Raised when a client is built for a custom environment whose base URL has a query or a fragment
+    InvalidBaseUrl(#[source] BaseUrlError),
+    /// Raised when a client is built for the production environment without opting in to it
+    ProductionNotAllowed,
+    /// Raised when the refresh token has expired, so a new login is required
+    RefreshTokenExpired,
+    /// Raised when the authentication state could not be loaded from or saved to a token store
+    TokenStore(#[source] std::io::Error),
+    /// Raised when a credential provider could not supply the credentials to log in with
+    CredentialsUnavailable(String),
+    /// Raised when a re-login is needed, but the previous one failed too recently to try again
+    /// * - `retry_after` - How long until a re-login may be attempted
+    ReloginRateLimited { retry_after: Duration },
+    /// Raised when a document is submitted for a different employer than the ones the client acts for
+    /// * - `expected` - The AFMs of the employers the client acts for
+    /// * - `found` - The employer AFM of the document
+    EmployerMismatch { expected: Vec<Afm>, found: Afm },
+    /// Raised when a document names an employer, but the client knows no employer it acts for to check it against
+    UnknownSessionEmployer(Afm),
+    /// Raised when a client pool has no entry for the employer AFM of a call
+    UnknownEmployer(Afm),
+    /// Raised when a client pool cannot route a document, because it does not name its employer
+    MissingEmployer,
     /// Raised when an API request fails due to an authentication error
-    AuthenticationFailed(#[source] reqwest::Error, ErganiError),
-    /// Raised when an API request fails due to an unknown error
-    General(#[source] reqwest::Error, ErganiError),
+    AuthenticationFailed(ErganiError),
     /// Raised when an API request fails due to a 404 error
-    NotFound(#[source] reqwest::Error, ErganiError),
+    NotFound(ErganiError),
+    /// Raised when an API request fails due to any other error response
+    Api(ErganiError),
+}
+
+impl ErganiClientError {
+    /// Returns the HTTP status of the response that caused the error, if there was one
+    pub fn status(&self) -> Option<StatusCode> {
+        self.ergani_error().map(ErganiError::status)
+    }
+
+    /// Returns the error response of the Ergani API, if the error was caused by one
+    pub fn ergani_error(&self) -> Option<&ErganiError> {
+        match self {
+            ErganiClientError::AuthenticationFailed(ergani_error)
+            | ErganiClientError::NotFound(ergani_error)
+            | ErganiClientError::Api(ergani_error) => Some(ergani_error),
+            _ => None,
+        }
+    }
 }
 
-impl Display for APIError {
+impl From<reqwest::Error> for ErganiClientError {
+    fn from(error: reqwest::Error) -> Self {
+        if error.is_builder() {
+            ErganiClientError::Configuration(error)
+        } else {
+            ErganiClientError::from(TransportError::from(error))
+        }
+    }
+}
+
+impl From<TransportError> for ErganiClientError {
+    fn from(error: TransportError) -> Self {
+        match error.kind() {
+            TransportErrorKind::Timeout => ErganiClientError::Timeout(error),
+            _ => ErganiClientError::Network(error),
+        }
+    }
+}
+
+impl Display for ErganiClientError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            APIError::AuthenticationFailed(_, ergani_error) => {
-                write!(f, "Authentication failed: {}", ergani_error.message())
+            ErganiClientError::Configuration(error) => {
+                write!(f, "Invalid client configuration: {error}")
+            }
+            ErganiClientError::Network(error) => write!(f, "Network error: {error}"),
+            ErganiClientError::Timeout(error) => write!(f, "Request timed out: {error}"),
+            ErganiClientError::Serialization(error) => {
+                write!(f, "Failed to serialize request: {error}")
+            }
+            ErganiClientError::Deserialization { source, .. } => {
+                write!(f, "Failed to deserialize response: {source}")
             }
-            APIError::General(_, ergani_error) => {
-                write!(f, "{}", ergani_error.message())
+            ErganiClientError::Validation(message) => write!(f, "Validation failed: {message}"),
+            ErganiClientError::InvalidDocuments(errors) => {
+                write!(f, "Invalid documents: {errors}")
             }
-            APIError::NotFound(_, ergani_error) => {
-                write!(f, "Resource not found: {}", ergani_error.message())
+            ErganiClientError::InvalidBaseUrl(error) => write!(f, "{error}"),
+            ErganiClientError::ProductionNotAllowed => write!(
+                f,
+                "Refusing to use the production Ergani API without an explicit opt-in"
+            ),
+            ErganiClientError::RefreshTokenExpired => {
+                write!(f, "The refresh token has expired, log in again")
+            }
+            ErganiClientError::TokenStore(error) => write!(f, "Token store error: {error}"),
+            ErganiClientError::CredentialsUnavailable(message) => {
+                write!(f, "Credentials unavailable: {message}")
+            }
+            ErganiClientError::ReloginRateLimited { retry_after } => write!(
+                f,
+                "Re-login failed recently, retry in {} seconds",
+                retry_after.as_secs()
+            ),
+            ErganiClientError::EmployerMismatch { expected, found } => write!(
+                f,
+                "The document is for employer {found}, but the client acts for {}; \
+                 set `allow_any_employer` for accounts authorized for every employer",
+                expected
+                    .iter()
+                    .map(Afm::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ErganiClientError::UnknownSessionEmployer(afm) => write!(
+                f,
+                "The document is for employer {afm}, but the client does not know which employers it acts for; \
+                 configure `employer_tax_identification_numbers`, log in with an employer AFM or set \
+                 `allow_any_employer`"
+            ),
+            ErganiClientError::UnknownEmployer(afm) => {
+                write!(f, "No client is registered for employer {afm}")
+            }
+            ErganiClientError::InvalidAccessToken(_) => write!(
+                f,
+                "The access token has characters that cannot be sent in an Authorization header"
+            ),
+            ErganiClientError::MissingEmployer => write!(
+                f,
+                "The document does not name its employer, submit it with `submit_for`"
+            ),
+            ErganiClientError::AuthenticationFailed(ergani_error) => write!(
+                f,
+                "Authentication failed ({}): {}",
+                ergani_error.status(),
+                ergani_error.message()
+            ),
+            ErganiClientError::NotFound(ergani_error) => write!(
+                f,
+                "Resource not found ({}): {}",
+                ergani_error.status(),
+                ergani_error.message()
+            ),
+            ErganiClientError::Api(ergani_error) => {
+                write!(f, "{} ({})", ergani_error.message(), ergani_error.status())
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ergani_error_from_json_response() {
+        let body = r#"{"message":"Invalid employer "}"#.to_string();
+        let ergani_error = ErganiError::from_response(StatusCode::BAD_REQUEST, body.clone());
+
+        assert_eq!(ergani_error.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(ergani_error.message(), "Invalid employer");
+        assert_eq!(ergani_error.body(), body);
+    }
+
+    #[test]
+    fn test_ergani_error_from_non_json_response() {
+        let body = "<html>Service Unavailable</html>".to_string();
+        let ergani_error =
+            ErganiError::from_response(StatusCode::SERVICE_UNAVAILABLE, body.clone());
+
+        assert_eq!(ergani_error.message(), body);
+        assert_eq!(ergani_error.body(), body);
+    }
+
+    #[test]
+    fn test_ergani_client_error_exposes_status() {
+        let ergani_error = ErganiError::from_response(StatusCode::NOT_FOUND, "".to_string());
+        let error = ErganiClientError::NotFound(ergani_error);
+
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+        assert_eq!(error.to_string(), "Resource not found (404 Not Found): ");
+    }
+}
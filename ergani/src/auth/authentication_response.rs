@@ -1,28 +1,30 @@
 use chrono::{DateTime, Utc};
 
+use crate::secret::SecretString;
+
 #[derive(serde::Deserialize)]
 pub struct AuthenticationResponse {
     #[serde(rename = "accessToken")]
-    access_token: String,
+    access_token: SecretString,
     #[serde(rename = "accessTokenExpired")]
     access_token_expired: i64,
     #[serde(rename = "refreshToken")]
-    refresh_token: String,
+    refresh_token: SecretString,
     #[serde(rename = "refreshTokenExpired")]
     refresh_token_expired: DateTime<Utc>,
 }
 
 impl AuthenticationResponse {
-    pub fn access_token(&self) -> String {
-        self.access_token.to_string()
+    pub fn access_token(&self) -> &SecretString {
+        &self.access_token
     }
 
     pub fn access_token_expired(&self) -> i64 {
         self.access_token_expired
     }
 
-    pub fn refresh_token(&self) -> String {
-        self.refresh_token.to_string()
+    pub fn refresh_token(&self) -> &SecretString {
+        &self.refresh_token
     }
 
     pub fn refresh_token_expired(&self) -> DateTime<Utc> {
@@ -1,25 +1,47 @@
-use crate::api_error::{APIError, ErganiError};
+use crate::api_error::{ErganiClientError, ErganiError};
 use crate::endpoint::{AUTHENTICATION_ENDPOINT, AUTHENTICATION_REFRESH_ENDPOINT};
-use anyhow::{bail, Result};
+use crate::events::{ErganiEventKind, EventEmitter};
+use crate::internal::clock_skew::ClockSkew;
+use crate::internal::deserializers::deserialize_body;
+use crate::transport::{
+    ErganiTransport, ReqwestTransport, TransportError, TransportRequest, TransportResponse,
+};
 use bon::{bon, Builder};
-use chrono::{DateTime, Utc};
-use serde_json::json;
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::Method;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 use crate::auth::authentication_response::AuthenticationResponse;
 use crate::auth::login_payload::LoginPayload;
+use crate::auth::token_info::TokenInfo;
+use crate::auth::user_type::UserType;
+use crate::models::types::afm::Afm;
+use crate::secret::SecretString;
 
 /// Authentication handler for the Ergani API
+///
+/// The state can be serialized to persist it between process restarts, e.g. with a
+/// [TokenStore](crate::auth::token_store::TokenStore). The tokens are [SecretString]s, which are redacted
+/// in Debug output but serialized in plain text.
 #[allow(dead_code)]
-#[derive(Clone, Builder)]
+#[derive(Clone, Debug, Builder, Serialize, Deserialize)]
 pub struct ErganiAuthenticationState {
-    access_token: String,
+    #[builder(into)]
+    access_token: SecretString,
     access_token_expired: i64,
-    refresh_token: String,
+    #[builder(into)]
+    refresh_token: SecretString,
     refresh_token_expired: DateTime<Utc>,
+    #[builder(default)]
+    #[serde(default)]
+    user_type: UserType,
+    #[serde(default)]
+    employer_tax_identification_number: Option<Afm>,
 }
 
 impl ErganiAuthenticationState {
-    pub fn access_token(&self) -> &str {
+    pub fn access_token(&self) -> &SecretString {
         &self.access_token
     }
 
@@ -27,31 +49,118 @@ impl ErganiAuthenticationState {
         self.access_token_expired
     }
 
-    pub fn refresh_token(&self) -> &str {
+    /// Returns when the access token expires, or `None` if `access_token_expired` is not a valid Unix timestamp
+    pub fn access_token_expires_at(&self) -> Option<DateTime<Utc>> {
+        DateTime::<Utc>::from_timestamp(self.access_token_expired, 0)
+    }
+
+    /// Returns what the access token says about itself, decoded from its claims if it is a JWT
+    ///
+    /// See [TokenInfo::from_auth_state]
+    pub fn token_info(&self) -> TokenInfo {
+        TokenInfo::from_auth_state(self)
+    }
+
+    /// Returns whether the access token expires within `margin` from `now`, or has no valid expiry
+    ///
+    /// # Arguments
+    /// * `margin` - How far ahead of the expiry the token is considered expired
+    /// * `now` - The current time, e.g. corrected for the skew of the server clock
+    pub fn access_token_expires_within(&self, margin: TimeDelta, now: DateTime<Utc>) -> bool {
+        match self.access_token_expires_at() {
+            Some(expires_at) => expires_at - margin <= now,
+            None => true,
+        }
+    }
+
+    pub fn refresh_token(&self) -> &SecretString {
         &self.refresh_token
     }
 
     pub fn refresh_token_expired(&self) -> &DateTime<Utc> {
         &self.refresh_token_expired
     }
+
+    /// Returns the category of the user that logged in
+    pub fn user_type(&self) -> &UserType {
+        &self.user_type
+    }
+
+    /// Returns the AFM of the employer the user acts for, if it was given at login
+    pub fn employer_tax_identification_number(&self) -> Option<&Afm> {
+        self.employer_tax_identification_number.as_ref()
+    }
+
+    /// Returns whether the refresh token has expired by the local clock, so that only a new login can renew the state
+    ///
+    /// See [ErganiAuthenticationState::is_refresh_token_expired_at] to correct for the skew of the server clock.
+    pub fn is_refresh_token_expired(&self) -> bool {
+        self.is_refresh_token_expired_at(Utc::now())
+    }
+
+    /// Returns whether the refresh token has expired at `now`, so that only a new login can renew the state
+    ///
+    /// # Arguments
+    /// * `now` - The current time, e.g. corrected for the skew of the server clock
+    pub fn is_refresh_token_expired_at(&self, now: DateTime<Utc>) -> bool {
+        self.refresh_token_expired <= now
+    }
+}
+
+/// The body of a login request, borrowing the password instead of copying it
+#[derive(Serialize)]
+#[serde(rename_all = "PascalCase")]
+struct LoginRequest<'a> {
+    username: &'a str,
+    password: &'a SecretString,
+    user_type: &'a str,
+}
+
+/// The body of a refresh request, borrowing the refresh token instead of copying it
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct RefreshRequest<'a> {
+    refresh_token: &'a SecretString,
 }
 
 #[derive(Clone)]
 pub struct ErganiAuthenticator {
     base_url: String,
-    http_client: reqwest::Client,
+    transport: Arc<dyn ErganiTransport>,
+    events: Option<EventEmitter>,
+    clock_skew: Option<Arc<ClockSkew>>,
 }
 
 #[bon]
 impl ErganiAuthenticator {
+    /// Creates a new authenticator for the Ergani API
+    ///
+    /// # Arguments
+    /// * `base_url` - The base URL of the Ergani API
+    /// * `transport` - The transport to send requests with. Prefer [crate::client::ErganiClient::authenticator]
+    ///   to share the transport and configuration of a client. Defaults to a [ReqwestTransport] with an unconfigured client.
     #[builder]
-    pub fn new(base_url: String) -> Self {
+    pub fn new(base_url: String, transport: Option<Arc<dyn ErganiTransport>>) -> Self {
         ErganiAuthenticator {
             base_url,
-            http_client: reqwest::Client::new(),
+            transport: transport.unwrap_or_else(|| Arc::new(ReqwestTransport::default())),
+            events: None,
+            clock_skew: None,
         }
     }
 
+    /// Emits the logins and refreshes of this authenticator to the subscribers of a client
+    pub(crate) fn with_events(mut self, events: EventEmitter) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Measures the clock skew of a client from the responses of this authenticator too
+    pub(crate) fn with_clock_skew(mut self, clock_skew: Arc<ClockSkew>) -> Self {
+        self.clock_skew = Some(clock_skew);
+        self
+    }
+
     /// Creates a new instance of the ErganiAuthenticator with the specified base URL
     ///
     /// # Arguments
@@ -59,40 +168,35 @@ impl ErganiAuthenticator {
     ///
     /// # Returns
     /// * `ErganiAuthenticationState` - The authentication state of the Ergani API
-    pub async fn login(&self, login_payload: LoginPayload) -> Result<ErganiAuthenticationState> {
+    pub async fn login(
+        &self,
+        login_payload: LoginPayload,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
         let url = format!("{}{AUTHENTICATION_ENDPOINT}", self.base_url);
 
+        let body = serde_json::to_value(LoginRequest {
+            username: login_payload.username(),
+            password: login_payload.password(),
+            user_type: login_payload.user_type().code(),
+        })
+        .map_err(ErganiClientError::Serialization)?;
+
         let response = self
-            .http_client
-            .post(&url)
-            .json(&json!({
-                "Username": login_payload.username(),
-                "Password": login_payload.password(),
-                "UserType": "02"
-            }))
-            .send()
+            ._send(TransportRequest::new(Method::POST, url, Some(body)))
             .await?;
 
-        if response.status().is_success() {
-            let authentication_response: AuthenticationResponse =
-                response.json::<AuthenticationResponse>().await?;
-
-            let authentication_state = ErganiAuthenticationState::builder()
-                .access_token(authentication_response.access_token())
-                .access_token_expired(authentication_response.access_token_expired())
-                .refresh_token(authentication_response.refresh_token())
-                .refresh_token_expired(authentication_response.refresh_token_expired())
-                .build();
-
-            Ok(authentication_state)
-        } else {
-            let original_error = response.error_for_status_ref().unwrap_err();
-            let error_text = response.text().await?.to_string();
-            let ergani_error = ErganiError {
-                message: error_text,
-            };
-            bail!(APIError::AuthenticationFailed(original_error, ergani_error))
-        }
+        let auth_state = self._extract_authentication_state(
+            response,
+            login_payload.user_type().clone(),
+            login_payload.employer_tax_identification_number().cloned(),
+        )?;
+
+        self._emit(ErganiEventKind::LoggedIn {
+            username: login_payload.username().to_string(),
+            auth_state: auth_state.clone(),
+        });
+
+        Ok(auth_state)
     }
 
     /// Refreshes the authentication token using the provided authentication state
@@ -104,41 +208,169 @@ impl ErganiAuthenticator {
     /// * `Result<ErganiAuthenticationState>` - A new authentication state with refreshed tokens
     ///
     /// # Errors
-    /// * Returns `APIError::AuthenticationFailed` if the refresh request fails or returns an error response
+    /// * Returns `ErganiClientError::AuthenticationFailed` if the refresh request returns an error response
     pub async fn refresh(
         &self,
         auth_state: &ErganiAuthenticationState,
-    ) -> Result<ErganiAuthenticationState> {
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
         let url = format!("{}{AUTHENTICATION_REFRESH_ENDPOINT}", self.base_url);
 
-        let response = self
-            .http_client
-            .post(&url)
-            .json(&json!({
-                "refreshToken": auth_state.refresh_token()
-            }))
-            .send()
-            .await?;
+        let body = serde_json::to_value(RefreshRequest {
+            refresh_token: auth_state.refresh_token(),
+        })
+        .map_err(ErganiClientError::Serialization)?;
 
-        if response.status().is_success() {
-            let authentication_response: AuthenticationResponse =
-                response.json::<AuthenticationResponse>().await?;
-
-            let authentication_state = ErganiAuthenticationState::builder()
-                .access_token(authentication_response.access_token())
-                .access_token_expired(authentication_response.access_token_expired())
-                .refresh_token(authentication_response.refresh_token())
-                .refresh_token_expired(authentication_response.refresh_token_expired())
-                .build();
-
-            Ok(authentication_state)
-        } else {
-            let original_error = response.error_for_status_ref().unwrap_err();
-            let error_text = response.text().await?.to_string();
-            let ergani_error = ErganiError {
-                message: error_text,
-            };
-            bail!(APIError::AuthenticationFailed(original_error, ergani_error))
+        let refreshed_auth_state = match self
+            ._send(TransportRequest::new(Method::POST, url, Some(body)))
+            .await
+        {
+            Ok(response) => self._extract_authentication_state(
+                response,
+                auth_state.user_type().clone(),
+                auth_state.employer_tax_identification_number().cloned(),
+            ),
+            Err(error) => Err(error.into()),
+        };
+
+        match &refreshed_auth_state {
+            Ok(refreshed_auth_state) => self._emit(ErganiEventKind::TokenRefreshed {
+                auth_state: refreshed_auth_state.clone(),
+            }),
+            Err(error) => self._emit(ErganiEventKind::RefreshFailed {
+                error: error.to_string(),
+            }),
+        }
+
+        refreshed_auth_state
+    }
+
+    async fn _send(&self, request: TransportRequest) -> Result<TransportResponse, TransportError> {
+        let response = self.transport.send(request).await?;
+
+        if let Some(clock_skew) = &self.clock_skew {
+            clock_skew.record(&response.headers);
+        }
+
+        Ok(response)
+    }
+
+    fn _emit(&self, kind: ErganiEventKind) {
+        if let Some(events) = &self.events {
+            events.emit(kind);
         }
     }
+
+    /// Extracts the authentication state from a response of the authentication endpoints
+    ///
+    /// # Arguments
+    /// * `response` - The response of the authentication endpoint
+    /// * `user_type` - The category of the user that logged in
+    /// * `employer_tax_identification_number` - The AFM of the employer the user acts for, if known
+    ///
+    /// # Errors
+    /// * Returns `ErganiClientError::AuthenticationFailed` if the response is an error response
+    fn _extract_authentication_state(
+        &self,
+        response: TransportResponse,
+        user_type: UserType,
+        employer_tax_identification_number: Option<Afm>,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        let TransportResponse { status, body, .. } = response;
+
+        if !status.is_success() {
+            let ergani_error = ErganiError::from_response(status, body);
+            return Err(ErganiClientError::AuthenticationFailed(ergani_error));
+        }
+
+        let authentication_response: AuthenticationResponse = deserialize_body(body)?;
+
+        Ok(ErganiAuthenticationState::builder()
+            .access_token(authentication_response.access_token().clone())
+            .access_token_expired(authentication_response.access_token_expired())
+            .refresh_token(authentication_response.refresh_token().clone())
+            .refresh_token_expired(authentication_response.refresh_token_expired())
+            .user_type(user_type)
+            .maybe_employer_tax_identification_number(employer_tax_identification_number)
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::tests::{auth_state, client_with_responses, load_fixture_as_text};
+    use reqwest::StatusCode;
+
+    const REFRESH_MARGIN: TimeDelta = TimeDelta::seconds(60);
+
+    fn authentication_response() -> TransportResponse {
+        TransportResponse::new(
+            StatusCode::OK,
+            load_fixture_as_text("authentication_response_fixture.json"),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_login_sends_user_type_and_carries_it_through_refresh() {
+        let (client, transport) =
+            client_with_responses(vec![authentication_response(), authentication_response()]);
+        let authenticator = client.authenticator();
+        let login_payload = LoginPayload::builder()
+            .username("username".to_string())
+            .password("password".to_string())
+            .user_type(UserType::Employer)
+            .employer_tax_identification_number("123456783".parse().unwrap())
+            .build();
+
+        let auth_state = authenticator.login(login_payload).await.unwrap();
+        let refreshed_auth_state = authenticator.refresh(&auth_state).await.unwrap();
+
+        let requests = transport.requests();
+        assert_eq!(
+            requests[0].body,
+            Some(serde_json::json!({
+                "Username": "username",
+                "Password": "password",
+                "UserType": "01"
+            }))
+        );
+        assert_eq!(
+            requests[1].body,
+            Some(serde_json::json!({"refreshToken": "refreshed-refresh-token"}))
+        );
+        assert_eq!(refreshed_auth_state.user_type(), &UserType::Employer);
+        assert_eq!(
+            refreshed_auth_state
+                .employer_tax_identification_number()
+                .map(Afm::as_str),
+            Some("123456783")
+        );
+    }
+
+    #[test]
+    fn test_expires_within_is_false_for_fresh_token() {
+        let auth_state = auth_state()
+            .access_token_expires_at(Utc::now() + TimeDelta::minutes(10))
+            .build();
+
+        assert!(!auth_state.access_token_expires_within(REFRESH_MARGIN, Utc::now()));
+    }
+
+    #[test]
+    fn test_expires_within_is_true_inside_margin() {
+        let auth_state = auth_state()
+            .access_token_expires_at(Utc::now() + TimeDelta::seconds(30))
+            .build();
+
+        assert!(auth_state.access_token_expires_within(REFRESH_MARGIN, Utc::now()));
+    }
+
+    #[test]
+    fn test_expires_within_is_true_for_expired_token() {
+        let auth_state = auth_state()
+            .access_token_expires_at(Utc::now() - TimeDelta::minutes(1))
+            .build();
+
+        assert!(auth_state.access_token_expires_within(REFRESH_MARGIN, Utc::now()));
+    }
 }
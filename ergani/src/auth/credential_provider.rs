@@ -0,0 +1,147 @@
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use zeroize::Zeroizing;
+
+use crate::api_error::ErganiClientError;
+use crate::auth::login_payload::LoginPayload;
+
+/// Supplies the credentials used to log in to the Ergani API again once the refresh token has expired
+///
+/// Credentials are requested on every re-login, so providers may return rotated credentials.
+#[async_trait]
+pub trait CredentialProvider: Send + Sync {
+    /// Returns the credentials to log in with
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::CredentialsUnavailable]` - Raised if the credentials could not be read
+    async fn credentials(&self) -> Result<LoginPayload, ErganiClientError>;
+}
+
+/// A [CredentialProvider] that always returns the same credentials
+#[derive(Clone)]
+pub struct StaticCredentialProvider {
+    login_payload: LoginPayload,
+}
+
+impl StaticCredentialProvider {
+    /// Creates a provider that returns the given credentials
+    ///
+    /// # Arguments
+    /// * - `login_payload` - The credentials to log in with
+    pub fn new(login_payload: LoginPayload) -> Self {
+        StaticCredentialProvider { login_payload }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for StaticCredentialProvider {
+    async fn credentials(&self) -> Result<LoginPayload, ErganiClientError> {
+        Ok(self.login_payload.clone())
+    }
+}
+
+/// A [CredentialProvider] that reads the credentials from environment variables
+/// * - `username_variable` - The variable holding the username. Defaults to `ERGANI_USERNAME`.
+/// * - `password_variable` - The variable holding the password. Defaults to `ERGANI_PASSWORD`.
+#[derive(Clone, Debug, bon::Builder)]
+pub struct EnvCredentialProvider {
+    #[builder(into, default = "ERGANI_USERNAME".to_string())]
+    username_variable: String,
+    #[builder(into, default = "ERGANI_PASSWORD".to_string())]
+    password_variable: String,
+}
+
+impl Default for EnvCredentialProvider {
+    fn default() -> Self {
+        EnvCredentialProvider::builder().build()
+    }
+}
+
+impl EnvCredentialProvider {
+    fn _read_variable(variable: &str) -> Result<String, ErganiClientError> {
+        std::env::var(variable).map_err(|error| {
+            ErganiClientError::CredentialsUnavailable(format!("{variable}: {error}"))
+        })
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for EnvCredentialProvider {
+    async fn credentials(&self) -> Result<LoginPayload, ErganiClientError> {
+        Ok(LoginPayload::builder()
+            .username(Self::_read_variable(&self.username_variable)?)
+            .password(Self::_read_variable(&self.password_variable)?)
+            .build())
+    }
+}
+
+/// A [CredentialProvider] that reads the credentials from a JSON file,
+/// such as `{"username": "...", "password": "..."}`
+#[derive(Clone, Debug)]
+pub struct FileCredentialProvider {
+    path: PathBuf,
+}
+
+impl FileCredentialProvider {
+    /// Creates a provider that reads the credentials from the given file
+    ///
+    /// # Arguments
+    /// * - `path` - The path of the JSON file
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileCredentialProvider { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl CredentialProvider for FileCredentialProvider {
+    async fn credentials(&self) -> Result<LoginPayload, ErganiClientError> {
+        let unavailable = |error: &dyn std::fmt::Display| {
+            ErganiClientError::CredentialsUnavailable(format!("{}: {error}", self.path.display()))
+        };
+
+        let path = self.path.clone();
+        let contents = tokio::task::spawn_blocking(move || std::fs::read(path).map(Zeroizing::new))
+            .await
+            .unwrap_or_else(|error| Err(std::io::Error::other(error)))
+            .map_err(|error| unavailable(&error))?;
+
+        serde_json::from_slice(&contents).map_err(|error| unavailable(&error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_env_credential_provider_reports_missing_variable() {
+        let provider = EnvCredentialProvider::builder()
+            .username_variable("ERGANI_TEST_MISSING_USERNAME")
+            .build();
+
+        let error = provider.credentials().await.err().unwrap();
+
+        assert!(matches!(
+            error,
+            ErganiClientError::CredentialsUnavailable(message)
+                if message.starts_with("ERGANI_TEST_MISSING_USERNAME")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_file_credential_provider_reads_json_file() {
+        let path =
+            std::env::temp_dir().join(format!("ergani-credentials-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"username": "user", "password": "pass"}"#).unwrap();
+
+        let credentials = FileCredentialProvider::new(&path)
+            .credentials()
+            .await
+            .unwrap();
+
+        assert_eq!(credentials.username(), "user");
+        assert_eq!(credentials.password().expose_secret(), "pass");
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,7 +1,24 @@
-#[derive(bon::Builder, Clone)]
+use crate::auth::user_type::UserType;
+use crate::models::types::afm::Afm;
+use crate::secret::SecretString;
+
+/// The credentials used to log in to the Ergani API
+/// * - `username` - The Ergani username
+/// * - `password` - The Ergani password, kept as a [SecretString]
+/// * - `user_type` - The category of the user. Defaults to [UserType::External].
+/// * - `employer_tax_identification_number` - The AFM of the employer the user acts for, if known
+///
+/// Sessions logged in with an employer AFM refuse submissions for other employers.
+#[derive(bon::Builder, Clone, Debug, serde::Deserialize)]
 pub struct LoginPayload {
     username: String,
-    password: String,
+    #[builder(into)]
+    password: SecretString,
+    #[builder(default)]
+    #[serde(default)]
+    user_type: UserType,
+    #[serde(default)]
+    employer_tax_identification_number: Option<Afm>,
 }
 
 impl LoginPayload {
@@ -9,7 +26,22 @@ impl LoginPayload {
         &self.username
     }
 
-    pub fn password(&self) -> &str {
+    pub fn password(&self) -> &SecretString {
         &self.password
     }
+
+    pub fn user_type(&self) -> &UserType {
+        &self.user_type
+    }
+
+    pub fn employer_tax_identification_number(&self) -> Option<&Afm> {
+        self.employer_tax_identification_number.as_ref()
+    }
+
+    /// Returns the payload acting for the given employer, unless it already names one
+    pub(crate) fn or_employer_tax_identification_number(mut self, afm: &Afm) -> Self {
+        self.employer_tax_identification_number
+            .get_or_insert_with(|| afm.clone());
+        self
+    }
 }
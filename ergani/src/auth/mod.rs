@@ -1,3 +1,8 @@
 pub mod authentication_response;
 pub mod authenticator;
+pub mod credential_provider;
 pub mod login_payload;
+pub(crate) mod relogin_limiter;
+pub mod token_info;
+pub mod token_store;
+pub mod user_type;
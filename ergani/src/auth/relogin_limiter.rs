@@ -0,0 +1,130 @@
+use std::future::Future;
+use std::time::{Duration, Instant};
+
+use tokio::sync::Mutex;
+
+use crate::api_error::ErganiClientError;
+use crate::auth::authenticator::ErganiAuthenticationState;
+
+/// Limits how often a client logs in again, so that wrong credentials do not lock the account
+pub(crate) struct ReloginLimiter {
+    min_interval: Duration,
+    last_relogin: Mutex<Option<Relogin>>,
+}
+
+/// A re-login attempt and the authentication state it produced, if it succeeded
+struct Relogin {
+    attempted_at: Instant,
+    auth_state: Option<ErganiAuthenticationState>,
+}
+
+impl ReloginLimiter {
+    /// Creates a limiter that allows one re-login attempt per `min_interval`
+    pub(crate) fn new(min_interval: Duration) -> Self {
+        ReloginLimiter {
+            min_interval,
+            last_relogin: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Runs `login`, unless a re-login was attempted within the minimum interval
+    ///
+    /// Concurrent callers wait for a single attempt. If the last attempt succeeded recently, its
+    /// authentication state is returned instead of logging in again; if it failed recently, the call fails.
+    ///
+    /// # Arguments
+    /// * - `login` - Logs in and returns the new authentication state
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::ReloginRateLimited]` - Raised if the last attempt failed within the minimum interval
+    pub(crate) async fn relogin<F, Fut>(
+        &self,
+        login: F,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<ErganiAuthenticationState, ErganiClientError>>,
+    {
+        let mut last_relogin = self.last_relogin.lock().await;
+
+        if let Some(relogin) = last_relogin.as_ref() {
+            let elapsed = relogin.attempted_at.elapsed();
+
+            if elapsed < self.min_interval {
+                return match &relogin.auth_state {
+                    Some(auth_state) => Ok(auth_state.clone()),
+                    None => Err(ErganiClientError::ReloginRateLimited {
+                        retry_after: self.min_interval - elapsed,
+                    }),
+                };
+            }
+        }
+
+        let result = login().await;
+
+        *last_relogin = Some(Relogin {
+            attempted_at: Instant::now(),
+            auth_state: result.as_ref().ok().cloned(),
+        });
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::tests::auth_state;
+
+    #[tokio::test]
+    async fn test_failed_relogin_is_not_retried_within_interval() {
+        let limiter = ReloginLimiter::new(Duration::from_secs(60));
+
+        let first = limiter
+            .relogin(|| async { Err(ErganiClientError::Validation("wrong".to_string())) })
+            .await;
+        let second = limiter
+            .relogin(|| async { Ok(auth_state().access_token("new").build()) })
+            .await;
+
+        assert!(matches!(first, Err(ErganiClientError::Validation(_))));
+        assert!(matches!(
+            second,
+            Err(ErganiClientError::ReloginRateLimited { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recent_successful_relogin_is_reused() {
+        let limiter = ReloginLimiter::new(Duration::from_secs(60));
+
+        limiter
+            .relogin(|| async { Ok(auth_state().access_token("first").build()) })
+            .await
+            .unwrap();
+        let second = limiter
+            .relogin(|| async { Ok(auth_state().access_token("second").build()) })
+            .await
+            .unwrap();
+
+        assert_eq!(second.access_token().expose_secret(), "first");
+    }
+
+    #[tokio::test]
+    async fn test_relogin_is_allowed_after_interval() {
+        let limiter = ReloginLimiter::new(Duration::ZERO);
+
+        let _ = limiter
+            .relogin(|| async { Err(ErganiClientError::Validation("wrong".to_string())) })
+            .await;
+        let second = limiter
+            .relogin(|| async { Ok(auth_state().access_token("new").build()) })
+            .await;
+
+        assert!(second.is_ok());
+    }
+}
@@ -0,0 +1,214 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+
+use crate::auth::authenticator::ErganiAuthenticationState;
+use crate::auth::user_type::UserType;
+use crate::models::types::afm::Afm;
+
+/// The names of the claims the subject of a token may be found under, in order of preference
+const SUBJECT_CLAIMS: [&str; 4] = ["sub", "unique_name", "nameid", "name"];
+/// The names of the claims the user type of a token may be found under, in order of preference
+const USER_TYPE_CLAIMS: [&str; 3] = ["UserType", "user_type", "usertype"];
+/// The names of the claims the employer AFM of a token may be found under, in order of preference
+const EMPLOYER_CLAIMS: [&str; 4] = ["EmployerAfm", "employer_afm", "Afm", "afm"];
+
+/// Where the values of a [TokenInfo] come from
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenInfoSource {
+    /// The claims of the access token, completed with the authentication state for claims it lacks
+    Claims,
+    /// The authentication state alone, because the access token is not a decodable JWT
+    AuthenticationResponse,
+}
+
+/// What an access token says about itself
+/// * - `subject` - The user the token was issued to
+/// * - `user_type` - The category of the user
+/// * - `employer_tax_identification_number` - The AFM of the employer the user acts for, if present and valid
+/// * - `issued_at` - When the token was issued
+/// * - `expires_at` - When the token expires
+/// * - `source` - Whether the values were decoded from the token or taken from the authentication state
+///
+/// The claims are decoded without verifying the signature of the token, so they are only informative and
+/// must not be used for authorization decisions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TokenInfo {
+    subject: Option<String>,
+    user_type: Option<UserType>,
+    employer_tax_identification_number: Option<Afm>,
+    issued_at: Option<DateTime<Utc>>,
+    expires_at: Option<DateTime<Utc>>,
+    source: TokenInfoSource,
+}
+
+impl TokenInfo {
+    /// Decodes the claims of a JWT access token, without verifying its signature
+    ///
+    /// # Arguments
+    /// * - `access_token` - The access token
+    ///
+    /// # Returns
+    /// * - `Option<TokenInfo>` - The claims of the token, or `None` if it is not a decodable JWT
+    pub fn from_access_token(access_token: &str) -> Option<TokenInfo> {
+        let claims = decode_claims(access_token)?;
+
+        Some(TokenInfo {
+            subject: string_claim(&claims, &SUBJECT_CLAIMS),
+            user_type: string_claim(&claims, &USER_TYPE_CLAIMS).map(UserType::from),
+            employer_tax_identification_number: string_claim(&claims, &EMPLOYER_CLAIMS)
+                .and_then(|afm| afm.parse().ok()),
+            issued_at: timestamp_claim(&claims, "iat"),
+            expires_at: timestamp_claim(&claims, "exp"),
+            source: TokenInfoSource::Claims,
+        })
+    }
+
+    /// Describes the access token of an authentication state, from its claims if it is a decodable JWT,
+    /// falling back to the values of the authentication response for anything the claims lack
+    ///
+    /// # Arguments
+    /// * - `auth_state` - The authentication state holding the access token
+    pub fn from_auth_state(auth_state: &ErganiAuthenticationState) -> TokenInfo {
+        let fallback = TokenInfo {
+            subject: None,
+            user_type: Some(auth_state.user_type().clone()),
+            employer_tax_identification_number: auth_state
+                .employer_tax_identification_number()
+                .cloned(),
+            issued_at: None,
+            expires_at: auth_state.access_token_expires_at(),
+            source: TokenInfoSource::AuthenticationResponse,
+        };
+
+        match TokenInfo::from_access_token(auth_state.access_token().expose_secret()) {
+            Some(claims) => TokenInfo {
+                subject: claims.subject,
+                user_type: claims.user_type.or(fallback.user_type),
+                employer_tax_identification_number: claims
+                    .employer_tax_identification_number
+                    .or(fallback.employer_tax_identification_number),
+                issued_at: claims.issued_at,
+                expires_at: claims.expires_at.or(fallback.expires_at),
+                source: TokenInfoSource::Claims,
+            },
+            None => fallback,
+        }
+    }
+
+    pub fn subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+
+    pub fn user_type(&self) -> Option<&UserType> {
+        self.user_type.as_ref()
+    }
+
+    pub fn employer_tax_identification_number(&self) -> Option<&Afm> {
+        self.employer_tax_identification_number.as_ref()
+    }
+
+    pub fn issued_at(&self) -> Option<&DateTime<Utc>> {
+        self.issued_at.as_ref()
+    }
+
+    pub fn expires_at(&self) -> Option<&DateTime<Utc>> {
+        self.expires_at.as_ref()
+    }
+
+    pub fn source(&self) -> TokenInfoSource {
+        self.source
+    }
+}
+
+/// Decodes the payload of a JWT into its claims, without verifying the signature
+fn decode_claims(access_token: &str) -> Option<Map<String, Value>> {
+    let mut parts = access_token.split('.');
+    let (_header, payload, _signature) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let payload = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+
+    match serde_json::from_slice(&payload).ok()? {
+        Value::Object(claims) => Some(claims),
+        _ => None,
+    }
+}
+
+/// Returns the first of the named claims that holds a string or a number, as a string
+fn string_claim(claims: &Map<String, Value>, names: &[&str]) -> Option<String> {
+    names
+        .iter()
+        .filter_map(|name| claims.get(*name))
+        .find_map(|value| match value {
+            Value::String(value) => Some(value.clone()),
+            Value::Number(value) => Some(value.to_string()),
+            _ => None,
+        })
+}
+
+/// Returns the named claim as an instant, if it holds a Unix timestamp in seconds
+fn timestamp_claim(claims: &Map<String, Value>, name: &str) -> Option<DateTime<Utc>> {
+    DateTime::<Utc>::from_timestamp(claims.get(name)?.as_i64()?, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::tests::auth_state;
+
+    fn jwt(claims: Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"HS256","typ":"JWT"}"#);
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+
+        format!("{header}.{payload}.signature")
+    }
+
+    #[test]
+    fn test_token_info_decodes_claims() {
+        let access_token = jwt(serde_json::json!({
+            "sub": "username",
+            "UserType": "02",
+            "afm": "123456783",
+            "iat": 1700000000,
+            "exp": 1700003600
+        }));
+
+        let token_info = TokenInfo::from_auth_state(
+            &auth_state()
+                .access_token(access_token)
+                .user_type(UserType::Employer)
+                .build(),
+        );
+
+        assert_eq!(token_info.source(), TokenInfoSource::Claims);
+        assert_eq!(token_info.subject(), Some("username"));
+        assert_eq!(token_info.user_type(), Some(&UserType::External));
+        assert_eq!(
+            token_info
+                .employer_tax_identification_number()
+                .map(Afm::as_str),
+            Some("123456783")
+        );
+        assert_eq!(token_info.issued_at().unwrap().timestamp(), 1700000000);
+        assert_eq!(token_info.expires_at().unwrap().timestamp(), 1700003600);
+    }
+
+    #[test]
+    fn test_token_info_falls_back_to_authentication_response() {
+        let token_info = TokenInfo::from_auth_state(
+            &auth_state()
+                .access_token("opaque-token")
+                .user_type(UserType::Employer)
+                .build(),
+        );
+
+        assert_eq!(token_info.source(), TokenInfoSource::AuthenticationResponse);
+        assert_eq!(token_info.subject(), None);
+        assert_eq!(token_info.user_type(), Some(&UserType::Employer));
+        assert_eq!(token_info.expires_at().unwrap().timestamp(), 4102444800);
+    }
+}
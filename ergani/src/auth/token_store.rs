@@ -0,0 +1,265 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use fs4::fs_std::FileExt;
+
+use crate::api_error::ErganiClientError;
+use crate::auth::authenticator::ErganiAuthenticationState;
+
+/// Persists the authentication state, so that it survives process restarts
+///
+/// An [ErganiSession](crate::session::ErganiSession) with a token store loads the stored state instead of
+/// logging in, saves every refreshed state and clears the stored state once its refresh token has expired.
+#[async_trait]
+pub trait TokenStore: Send + Sync {
+    /// Loads the stored authentication state, if there is one
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::TokenStore]` - Raised if the stored state could not be read
+    async fn load(&self) -> Result<Option<ErganiAuthenticationState>, ErganiClientError>;
+
+    /// Stores the authentication state, replacing any previously stored one
+    ///
+    /// # Arguments
+    /// * - `auth_state` - The authentication state to store
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::TokenStore]` - Raised if the state could not be written
+    async fn save(&self, auth_state: &ErganiAuthenticationState) -> Result<(), ErganiClientError>;
+
+    /// Removes the stored authentication state, if there is one
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::TokenStore]` - Raised if the stored state could not be removed
+    async fn clear(&self) -> Result<(), ErganiClientError>;
+}
+
+/// A [TokenStore] that keeps the authentication state in memory, for the lifetime of the process
+#[derive(Default)]
+pub struct InMemoryTokenStore {
+    auth_state: Mutex<Option<ErganiAuthenticationState>>,
+}
+
+#[async_trait]
+impl TokenStore for InMemoryTokenStore {
+    async fn load(&self) -> Result<Option<ErganiAuthenticationState>, ErganiClientError> {
+        Ok(self.auth_state.lock().unwrap().clone())
+    }
+
+    async fn save(&self, auth_state: &ErganiAuthenticationState) -> Result<(), ErganiClientError> {
+        *self.auth_state.lock().unwrap() = Some(auth_state.clone());
+        Ok(())
+    }
+
+    async fn clear(&self) -> Result<(), ErganiClientError> {
+        *self.auth_state.lock().unwrap() = None;
+        Ok(())
+    }
+}
+
+/// A [TokenStore] that keeps the authentication state in a JSON file
+///
+/// Writes go to a temporary file that is renamed over the stored one, so readers never see a partially
+/// written state. Every access holds a lock on a sibling `.lock` file, so processes sharing the file,
+/// such as overlapping cron jobs, do not interfere. On Unix the file is only readable by its owner (`0600`).
+#[derive(Clone, Debug)]
+pub struct FileTokenStore {
+    path: PathBuf,
+}
+
+impl FileTokenStore {
+    /// Creates a store that keeps the authentication state in the given file
+    ///
+    /// # Arguments
+    /// * - `path` - The path of the file. Its parent directory must exist.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileTokenStore { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Runs `operation` on a blocking thread while holding the lock of the store
+    ///
+    /// # Arguments
+    /// * - `exclusive` - Whether the lock is exclusive, for writes, or shared, for reads
+    /// * - `operation` - The operation to run on the path of the store
+    async fn _with_lock<T, F>(&self, exclusive: bool, operation: F) -> Result<T, ErganiClientError>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Path) -> io::Result<T> + Send + 'static,
+    {
+        let path = self.path.clone();
+
+        tokio::task::spawn_blocking(move || {
+            let lock_file = open_private(&sibling_path(&path, "lock"), false)?;
+            if exclusive {
+                FileExt::lock_exclusive(&lock_file)?;
+            } else {
+                FileExt::lock_shared(&lock_file)?;
+            }
+
+            // The lock is released when `lock_file` is dropped
+            operation(&path)
+        })
+        .await
+        .unwrap_or_else(|error| Err(io::Error::other(error)))
+        .map_err(ErganiClientError::TokenStore)
+    }
+}
+
+#[async_trait]
+impl TokenStore for FileTokenStore {
+    async fn load(&self) -> Result<Option<ErganiAuthenticationState>, ErganiClientError> {
+        self._with_lock(false, |path| match fs::read(path) {
+            Ok(contents) => serde_json::from_slice(&contents)
+                .map(Some)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error),
+        })
+        .await
+    }
+
+    async fn save(&self, auth_state: &ErganiAuthenticationState) -> Result<(), ErganiClientError> {
+        let contents = serde_json::to_vec(auth_state).map_err(ErganiClientError::Serialization)?;
+
+        self._with_lock(true, move |path| {
+            let temporary_path = sibling_path(path, "tmp");
+
+            // A leftover temporary file may have looser permissions, so it is replaced rather than reused
+            match fs::remove_file(&temporary_path) {
+                Err(error) if error.kind() != io::ErrorKind::NotFound => return Err(error),
+                _ => {}
+            }
+
+            let mut temporary_file = open_private(&temporary_path, true)?;
+            temporary_file.write_all(&contents)?;
+            temporary_file.sync_all()?;
+
+            fs::rename(&temporary_path, path)
+        })
+        .await
+    }
+
+    async fn clear(&self) -> Result<(), ErganiClientError> {
+        self._with_lock(true, |path| match fs::remove_file(path) {
+            Err(error) if error.kind() != io::ErrorKind::NotFound => Err(error),
+            _ => Ok(()),
+        })
+        .await
+    }
+}
+
+/// Returns the path of a file next to `path`, with `extension` appended to its name
+fn sibling_path(path: &Path, extension: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+
+    path.with_file_name(file_name)
+}
+
+/// Opens a file for writing, creating it readable by its owner only
+///
+/// The permissions only apply to a file created by the call, so files that must be private are opened with
+/// `create_new`.
+///
+/// # Arguments
+/// * - `path` - The path of the file
+/// * - `create_new` - Whether opening fails if the file already exists, instead of opening it as it is
+fn open_private(path: &Path, create_new: bool) -> io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true);
+    if create_new {
+        options.create_new(true);
+    } else {
+        options.create(true);
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    options.open(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::tests::auth_state;
+
+    fn temporary_store(name: &str) -> FileTokenStore {
+        let directory =
+            std::env::temp_dir().join(format!("ergani-token-store-{name}-{}", std::process::id()));
+        fs::create_dir_all(&directory).unwrap();
+
+        FileTokenStore::new(directory.join("auth_state.json"))
+    }
+
+    #[tokio::test]
+    async fn test_file_token_store_round_trip() {
+        let store = temporary_store("round-trip");
+
+        assert!(store.load().await.unwrap().is_none());
+
+        store.save(&auth_state().build()).await.unwrap();
+        let loaded = store.load().await.unwrap().unwrap();
+        assert_eq!(loaded.access_token().expose_secret(), "access-token");
+        assert_eq!(loaded.refresh_token().expose_secret(), "refresh-token");
+
+        store.clear().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+        store.clear().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_token_store_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let store = temporary_store("private");
+        store.save(&auth_state().build()).await.unwrap();
+
+        let mode = fs::metadata(store.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+
+        store.clear().await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_file_token_store_replaces_leftover_temporary_file() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let store = temporary_store("leftover");
+        let temporary_path = sibling_path(store.path(), "tmp");
+        fs::write(&temporary_path, "leftover").unwrap();
+        fs::set_permissions(&temporary_path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        store.save(&auth_state().build()).await.unwrap();
+
+        let mode = fs::metadata(store.path()).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert!(store.load().await.unwrap().is_some());
+
+        store.clear().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_file_token_store_rejects_corrupt_state() {
+        let store = temporary_store("corrupt");
+        fs::write(store.path(), "not json").unwrap();
+
+        let error = store.load().await.err().unwrap();
+
+        assert!(matches!(error, ErganiClientError::TokenStore(_)));
+        store.clear().await.unwrap();
+    }
+}
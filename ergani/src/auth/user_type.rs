@@ -0,0 +1,83 @@
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// The category of user logging in to the Ergani API, sent as the `UserType` of the login request
+///
+/// The authentication section of the Ergani II Web Services API documentation (`POST /Authentication`) defines two
+/// user categories, `01` for employers and `02` for external users. There is no separate code for accountants or
+/// legal representatives: whoever logs in with external user credentials uses `02`. `Other` only keeps any code
+/// added to the API later usable without a new release.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum UserType {
+    /// An employer logging in with their own credentials (`01`)
+    Employer,
+    /// An external user logging in with their own Ergani credentials, e.g. an accountant authorized by one or more
+    /// employers (`02`)
+    #[default]
+    External,
+    /// A user category code not defined when this version was released
+    Other(String),
+}
+
+impl UserType {
+    /// Returns the code of the user category sent to the Ergani API
+    pub fn code(&self) -> &str {
+        match self {
+            UserType::Employer => "01",
+            UserType::External => "02",
+            UserType::Other(code) => code,
+        }
+    }
+}
+
+impl From<String> for UserType {
+    fn from(code: String) -> Self {
+        match code.as_str() {
+            "01" => UserType::Employer,
+            "02" => UserType::External,
+            _ => UserType::Other(code),
+        }
+    }
+}
+
+impl From<UserType> for String {
+    fn from(user_type: UserType) -> Self {
+        user_type.code().to_string()
+    }
+}
+
+impl Display for UserType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_type_round_trips_through_code() {
+        for user_type in [
+            UserType::Employer,
+            UserType::External,
+            UserType::Other("05".to_string()),
+        ] {
+            assert_eq!(UserType::from(user_type.code().to_string()), user_type);
+        }
+    }
+
+    #[test]
+    fn test_user_type_serializes_as_code() {
+        assert_eq!(
+            serde_json::to_string(&UserType::Employer).unwrap(),
+            r#""01""#
+        );
+        assert_eq!(
+            serde_json::from_str::<UserType>(r#""02""#).unwrap(),
+            UserType::External
+        );
+    }
+}
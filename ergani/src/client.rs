@@ -2,48 +2,95 @@
 
 use core::error;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::api_error::{APIError, ErganiError};
+use crate::api_error::{ErganiClientError, ErganiError};
 use crate::auth::authenticator::{ErganiAuthenticationState, ErganiAuthenticator};
+use crate::auth::credential_provider::CredentialProvider;
+use crate::auth::relogin_limiter::ReloginLimiter;
 use crate::endpoint::{
     DAILY_SCHEDULE_ENDPOINT, LOOKUP_SUBMISSIONS_ENDPOINT, OVERTIME_ENDPOINT, TRIAL_API_ENDPOINT,
     WEEKLY_SCHEDULE_ENDPOINT, WORK_CARD_ENDPOINT,
 };
+use crate::environment::ErganiEnvironment;
+use crate::ergani_document::{to_envelope, ErganiDocument};
 use crate::ergani_fetch_response::ErganiFetchResponse;
-use crate::internal::deserializers::deserialize_datetime;
+use crate::events::{ErganiEvent, ErganiEventKind, EventEmitter, DEFAULT_EVENT_CAPACITY};
+use crate::health_check::{
+    HealthCheckAuth, HealthCheckReport, HealthCheckStep, HealthCheckStepReport,
+};
+use crate::internal::clock_skew::ClockSkew;
+use crate::internal::deserializers::{deserialize_body, deserialize_datetime};
 use crate::models::company::company_daily_schedule::CompanyDailySchedule;
 use crate::models::company::company_overtime::CompanyOvertime;
 use crate::models::company::company_weekly_schedule::CompanyWeeklySchedule;
 use crate::models::company::company_work_card::CompanyWorkCard;
+use crate::models::types::afm::Afm;
+use crate::prepared_request::{PreparedRequest, REDACTED_TOKEN};
 use crate::responses::day_schedule_response::DayScheduleResponseRoot;
 use crate::responses::lookup_response::{LookupResponse, LookupRoot};
 use crate::responses::overtime_response::OvertimeResponseRoot;
 use crate::responses::week_schedule_response::WeekScheduleResponseRoot;
 use crate::responses::work_card_response::WorkCardResponseRoot;
-use anyhow::{bail, Result};
-use bon::Builder;
-use chrono::{DateTime, Utc};
-use reqwest::header::HeaderValue;
+use crate::retry_policy::{parse_retry_after, AttemptFailure, RetryPolicy};
+use crate::submission_outcome::SubmissionOutcome;
+use crate::transport::{
+    ErganiTransport, ReqwestTransport, TransportError, TransportErrorKind, TransportRequest,
+    TransportResponse,
+};
+use crate::validation::{ValidationErrors, ValidationIssue};
+use async_trait::async_trait;
+use bon::{bon, Builder};
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+pub use reqwest::{Certificate, Proxy};
 use reqwest::{Method, Request, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::{json, Value};
 use tokio::sync::Mutex;
-use tracing::{error, info};
+use tracing::{error, info, info_span, warn, Instrument};
 
 use crate::auth::{self, login_payload};
 
 #[derive(Clone)]
 pub struct ErganiClient {
-    base_url: String,
-    http_client: reqwest::Client,
+    environment: ErganiEnvironment,
+    transport: Arc<dyn ErganiTransport>,
+    retry_policy: RetryPolicy,
+    credential_provider: Option<Arc<dyn CredentialProvider>>,
+    relogin_limiter: Arc<ReloginLimiter>,
+    events: EventEmitter,
+    refresh_margin: TimeDelta,
+    clock_skew: Arc<ClockSkew>,
+    employer_tax_identification_numbers: Vec<Afm>,
+    allow_any_employer: bool,
+    validate_documents: bool,
+    auth_state_renewal: Option<Arc<dyn AuthStateRenewal>>,
+}
+
+/// Renews the authentication state of a request whose access token expires soon or was rejected, in place of
+/// the client refreshing it on its own
+///
+/// [ErganiSession](crate::session::ErganiSession) renews through its own refresh, so that concurrent requests of a
+/// session rejected with the same access token share a single refresh.
+#[async_trait]
+pub(crate) trait AuthStateRenewal: Send + Sync {
+    /// Renews the authentication state
+    ///
+    /// # Arguments
+    /// * - `stale_auth_state` - The authentication state the request was sent with
+    async fn renew(
+        &self,
+        stale_auth_state: &ErganiAuthenticationState,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError>;
 }
 
 /// Represents a submission response from the Ergani API
 /// * - `submission_id` - The unique identifier of the submission
 /// * - `protocol` - The protocol associated with the submission
 /// * - `submission_date` - The datetime of the submission
-#[derive(Deserialize, Debug)]
+#[derive(Builder, Deserialize, Debug, Clone)]
 pub struct SubmissionResponse {
     id: String,
     protocol: String,
@@ -51,6 +98,20 @@ pub struct SubmissionResponse {
     submit_date: DateTime<Utc>,
 }
 
+impl SubmissionResponse {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn protocol(&self) -> &str {
+        &self.protocol
+    }
+
+    pub fn submit_date(&self) -> &DateTime<Utc> {
+        &self.submit_date
+    }
+}
+
 #[derive(Builder)]
 struct ErganiRequestPayload {
     method: Method,
@@ -60,59 +121,332 @@ struct ErganiRequestPayload {
 
 #[derive(Builder)]
 struct ErganiRequestResponse {
-    response: Option<Response>,
+    response: Option<TransportResponse>,
     auth_state: ErganiAuthenticationState,
 }
 
+/// The user agent sent to the Ergani API unless a custom one is configured
+const DEFAULT_USER_AGENT: &str = "Ergani Rust Client";
+/// The default time allowed to establish a connection to the Ergani API
+const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+/// The default time allowed for a whole request to the Ergani API
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// The default minimum time between two attempts to log in again
+const DEFAULT_RELOGIN_INTERVAL: Duration = Duration::from_secs(300);
+/// The default time before the access token expires that it is refreshed ahead of a request
+const DEFAULT_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+/// How close to its expiry a refresh token that fails to refresh is considered expired, to tolerate clock skew
+const REFRESH_TOKEN_EXPIRY_TOLERANCE: TimeDelta = TimeDelta::minutes(5);
+
+#[bon]
 impl ErganiClient {
+    /// Creates a client for interacting with the Ergani API
+    ///
+    /// # Arguments:
+    /// * - `environment` - The Ergani API environment to talk to. Defaults to [ErganiEnvironment::Trial].
+    /// * - `allow_production` - Opts in to [ErganiEnvironment::Production]. Defaults to `false`.
+    /// * - `connect_timeout` - The time allowed to establish a connection. Defaults to 10 seconds.
+    /// * - `timeout` - The time allowed for a whole request, from connecting until the response body is read. Defaults to 30 seconds.
+    /// * - `proxy` - An HTTP proxy to send every request through
+    /// * - `root_certificates` - Extra root certificates to trust, e.g. the one of a TLS-inspecting corporate proxy
+    /// * - `user_agent` - The user agent sent with every request. Defaults to `Ergani Rust Client`.
+    /// * - `retry_policy` - How failed requests are retried. Defaults to [RetryPolicy::default].
+    /// * - `transport` - A custom transport to send every request through, e.g. one that returns canned responses in tests.
+    ///   When set, `connect_timeout`, `timeout`, `proxy`, `root_certificates` and `user_agent` are ignored.
+    /// * - `credential_provider` - Supplies the credentials to log in again with once the refresh token has expired.
+    ///   Without one, requests fail with [ErganiClientError::RefreshTokenExpired] instead.
+    /// * - `relogin_interval` - The minimum time between two attempts to log in again, so that wrong credentials
+    ///   do not lock the account. Defaults to 5 minutes.
+    /// * - `refresh_margin` - How long before the access token expires it is refreshed ahead of a request, instead of
+    ///   waiting for the Ergani API to reject it. Defaults to 60 seconds.
+    /// * - `employer_tax_identification_numbers` - The AFMs of the employers the client acts for, besides the one
+    ///   of the authentication state or its token claims. Submissions for other employers are rejected.
+    /// * - `allow_any_employer` - Submits documents for any employer, for accountant accounts authorized for
+    ///   several employers. Defaults to `false`.
+    /// * - `validate_documents` - Checks every document before submitting it and refuses the whole submission if
+    ///   any has issues, reporting all of them at once. Defaults to `true`.
+    /// * - `event_capacity` - How many events a subscriber may lag behind before it misses the oldest ones. Defaults to 64.
+    ///
+    /// # Errors:
+    /// * - `[ErganiClientError::Configuration]` - Raised if the HTTP client could not be built from the given configuration
+    /// * - `[ErganiClientError::InvalidBaseUrl]` - Raised if the base URL of a custom environment has a query or a fragment
+    /// * - `[ErganiClientError::ProductionNotAllowed]` - Raised if the environment is production and `allow_production` is not set
+    #[builder]
+    pub fn new(
+        #[builder(default)] environment: ErganiEnvironment,
+        #[builder(default)] allow_production: bool,
+        #[builder(default = DEFAULT_CONNECT_TIMEOUT)] connect_timeout: Duration,
+        #[builder(default = DEFAULT_TIMEOUT)] timeout: Duration,
+        proxy: Option<Proxy>,
+        #[builder(default)] root_certificates: Vec<Certificate>,
+        #[builder(into, default = DEFAULT_USER_AGENT.to_string())] user_agent: String,
+        #[builder(default)] retry_policy: RetryPolicy,
+        transport: Option<Arc<dyn ErganiTransport>>,
+        credential_provider: Option<Arc<dyn CredentialProvider>>,
+        #[builder(default = DEFAULT_RELOGIN_INTERVAL)] relogin_interval: Duration,
+        #[builder(default = DEFAULT_REFRESH_MARGIN)] refresh_margin: Duration,
+        #[builder(default)] employer_tax_identification_numbers: Vec<Afm>,
+        #[builder(default)] allow_any_employer: bool,
+        #[builder(default = true)] validate_documents: bool,
+        #[builder(default = DEFAULT_EVENT_CAPACITY)] event_capacity: usize,
+    ) -> Result<ErganiClient, ErganiClientError> {
+        environment
+            .check()
+            .map_err(ErganiClientError::InvalidBaseUrl)?;
+
+        if environment.is_production() && !allow_production {
+            return Err(ErganiClientError::ProductionNotAllowed);
+        }
+
+        info!(%environment, "Creating Ergani client");
+
+        let transport: Arc<dyn ErganiTransport> = match transport {
+            Some(transport) => transport,
+            None => {
+                let mut http_client_builder = reqwest::Client::builder()
+                    .user_agent(user_agent)
+                    .connect_timeout(connect_timeout)
+                    .timeout(timeout);
+
+                if let Some(proxy) = proxy {
+                    http_client_builder = http_client_builder.proxy(proxy);
+                }
+
+                for root_certificate in root_certificates {
+                    http_client_builder =
+                        http_client_builder.add_root_certificate(root_certificate);
+                }
+
+                Arc::new(ReqwestTransport::new(http_client_builder.build()?))
+            }
+        };
+
+        Ok(ErganiClient {
+            environment,
+            transport,
+            retry_policy,
+            credential_provider,
+            relogin_limiter: Arc::new(ReloginLimiter::new(relogin_interval)),
+            events: EventEmitter::new(event_capacity),
+            refresh_margin: TimeDelta::from_std(refresh_margin).unwrap_or(TimeDelta::MAX),
+            clock_skew: Arc::new(ClockSkew::default()),
+            employer_tax_identification_numbers,
+            allow_any_employer,
+            validate_documents,
+            auth_state_renewal: None,
+        })
+    }
+
     /// A client for interacting with the Ergani API
     /// * - `base_url` - The base URL of the Ergani API. Defaults to <https://trialeservices.yeka.gr/WebServicesAPI/api>.
+    ///
+    /// Unlike `ErganiClient::builder()`, this accepts the production base URL without opting in, as it always
+    /// did. A base URL that is not a valid base URL falls back to the trial environment.
+    #[deprecated(
+        note = "use `ErganiClient::builder()`, which guards against the production environment"
+    )]
     pub fn init(base_url: String) -> ErganiClient {
-        let client = reqwest::Client::builder()
-            .user_agent("Ergani Rust Client")
+        let environment = ErganiEnvironment::from_base_url(&base_url).unwrap_or_else(|error| {
+            warn!(%error, base_url, "Invalid Ergani API base URL, using the trial environment");
+            ErganiEnvironment::default()
+        });
+
+        ErganiClient::builder()
+            .environment(environment)
+            .allow_production(true)
             .build()
-            .unwrap();
+            .expect("failed to build the Ergani HTTP client")
+    }
+
+    /// Returns the Ergani API environment of this client
+    pub fn environment(&self) -> &ErganiEnvironment {
+        &self.environment
+    }
+
+    /// Returns how long before the access token expires it is refreshed ahead of a request
+    pub fn refresh_margin(&self) -> TimeDelta {
+        self.refresh_margin
+    }
 
+    /// Returns how far the clock of the Ergani API is ahead of the local clock, measured from the `Date` header
+    /// of the last response, or `None` before the first response
+    pub fn clock_skew(&self) -> Option<TimeDelta> {
+        self.clock_skew.skew()
+    }
+
+    /// Returns the current time according to the clock of the Ergani API, as far as it has been measured
+    pub fn server_now(&self) -> DateTime<Utc> {
+        self.clock_skew.server_now()
+    }
+
+    /// Returns an authenticator that shares the transport and configuration of this client
+    pub fn authenticator(&self) -> ErganiAuthenticator {
+        ErganiAuthenticator::builder()
+            .base_url(self.environment.base_url().to_string())
+            .transport(self.transport.clone())
+            .build()
+            .with_events(self.events.clone())
+            .with_clock_skew(self.clock_skew.clone())
+    }
+
+    /// Returns a client sharing the transport, configuration and events of this client, that logs in again
+    /// with another credential provider and has its own re-login rate limit
+    ///
+    /// # Arguments
+    /// * - `credential_provider` - The credentials of the other client
+    pub(crate) fn with_credential_provider(
+        &self,
+        credential_provider: Arc<dyn CredentialProvider>,
+    ) -> ErganiClient {
         ErganiClient {
-            http_client: client,
-            base_url,
+            credential_provider: Some(credential_provider),
+            relogin_limiter: Arc::new(ReloginLimiter::new(self.relogin_limiter.min_interval())),
+            ..self.clone()
         }
     }
 
-    /// Submits work card records (check-in, check-out) for employees to the Ergani API
+    /// Returns a client sharing everything with this client, that renews the authentication state of its requests
+    /// through `auth_state_renewal` instead of refreshing it on its own
+    ///
+    /// # Arguments
+    /// * - `auth_state_renewal` - Renews the authentication states of the requests
+    pub(crate) fn with_auth_state_renewal(
+        &self,
+        auth_state_renewal: Arc<dyn AuthStateRenewal>,
+    ) -> ErganiClient {
+        ErganiClient {
+            auth_state_renewal: Some(auth_state_renewal),
+            ..self.clone()
+        }
+    }
+
+    /// Subscribes to the authentication lifecycle and submission events of this client
+    ///
+    /// Events of clones of this client and of authenticators returned by [ErganiClient::authenticator]
+    /// are received too. A subscriber that lags behind by more than the event capacity misses the oldest events.
+    pub fn subscribe(&self) -> tokio::sync::broadcast::Receiver<ErganiEvent> {
+        self.events.subscribe()
+    }
+
+    /// Submits documents of any [ErganiDocument] type to the Ergani API
     ///
     /// # Arguments:
-    /// * - `company_work_cards[Vec<CompanyWorkCard>]` - A Vec of CompanyWorkCard instances to be submitted
+    /// * - `documents[Vec<D>]` - A Vec of documents to be submitted, wrapped in the envelope declared by `D`
     /// * - `auth_state` - The authentication state of the Ergani API
     ///
     /// # Returns:
-    /// * - `[Vec<SubmissionResponse>]` - A list of SubmissionResponse that were parsed from the API response
+    /// * - `[SubmissionOutcome]` - The `D::Response` list parsed from the API response and the current authentication state
     ///
     /// # Errors:
-    /// * - `[APIError::General]` - An error occurred while communicating with the Ergani API
-    /// * - `[APIError::AuthenticationError]` - Raised if there is an authentication error with the Ergani API
-    pub async fn submit_work_card(
+    /// * - `[ErganiClientError::Api]` - An error occurred while communicating with the Ergani API
+    /// * - `[ErganiClientError::AuthenticationFailed]` - Raised if there is an authentication error with the Ergani API
+    /// * - `[ErganiClientError::EmployerMismatch]` - Raised before sending anything if a document is for an employer
+    ///   the client does not act for
+    /// * - `[ErganiClientError::InvalidDocuments]` - Raised before sending anything with every issue of the documents,
+    ///   unless `validate_documents` is turned off
+    pub async fn submit<D: ErganiDocument>(
         &self,
-        company_work_cards: Vec<CompanyWorkCard>,
+        documents: Vec<D>,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<Vec<SubmissionResponse>> {
-        let params = serde_json::to_value(company_work_cards)?;
+    ) -> Result<SubmissionOutcome<D::Response>, ErganiClientError> {
+        self._check_employer(&documents, &auth_state)?;
+        self._validate_documents(&documents)?;
 
-        let request_payload = json!({
-            "Cards": {
-                "Card": params
-            }
+        let payload = self._submission_payload(&documents)?;
+        let response = self._request(&payload, &auth_state).await?;
+        let outcome = self._extract_submission_result::<D::Response>(response)?;
+
+        self.events.emit(ErganiEventKind::SubmissionAccepted {
+            endpoint: D::ENDPOINT.to_string(),
+            submissions: outcome.submissions().len(),
+            environment: self.environment.clone(),
         });
 
-        let payload = ErganiRequestPayload::builder()
-            .method(Method::POST)
-            .endpoint(WORK_CARD_ENDPOINT.to_string())
-            .body(request_payload)
-            .build();
-        let response = self._request(&payload, &auth_state).await?;
+        Ok(outcome)
+    }
 
-        self._extract_submission_result(response.response, auth_state)
-            .await
+    /// Builds the request that [ErganiClient::submit] would send for the documents, without sending it
+    ///
+    /// # Arguments:
+    /// * - `documents[&[D]]` - The documents to be submitted, wrapped in the envelope declared by `D`
+    ///
+    /// # Returns:
+    /// * - `[PreparedRequest]` - The HTTP method, full URL, headers with the access token redacted and JSON envelope of the request
+    ///
+    /// # Errors:
+    /// * - `[ErganiClientError::Serialization]` - Raised if the documents could not be serialized
+    pub fn prepare<D: ErganiDocument>(
+        &self,
+        documents: &[D],
+    ) -> Result<PreparedRequest, ErganiClientError> {
+        let payload = self._submission_payload(documents)?;
+        let mut request = self._build_request(&payload, REDACTED_TOKEN)?;
+
+        if let Some(authorization) = request.headers.get_mut(AUTHORIZATION) {
+            authorization.set_sensitive(false);
+        }
+
+        Ok(PreparedRequest::from(request))
+    }
+
+    /// Builds the request that [ErganiClient::submit_work_card] would send, without sending it
+    ///
+    /// See [ErganiClient::prepare]
+    pub fn prepare_work_card(
+        &self,
+        company_work_cards: &[CompanyWorkCard],
+    ) -> Result<PreparedRequest, ErganiClientError> {
+        self.prepare(company_work_cards)
+    }
+
+    /// Builds the request that [ErganiClient::submit_overtime] would send, without sending it
+    ///
+    /// See [ErganiClient::prepare]
+    pub fn prepare_overtime(
+        &self,
+        company_overtimes: &[CompanyOvertime],
+    ) -> Result<PreparedRequest, ErganiClientError> {
+        self.prepare(company_overtimes)
+    }
+
+    /// Builds the request that [ErganiClient::submit_daily_schedule] would send, without sending it
+    ///
+    /// See [ErganiClient::prepare]
+    pub fn prepare_daily_schedule(
+        &self,
+        company_daily_schedules: &[CompanyDailySchedule],
+    ) -> Result<PreparedRequest, ErganiClientError> {
+        self.prepare(company_daily_schedules)
+    }
+
+    /// Builds the request that [ErganiClient::submit_weekly_schedule] would send, without sending it
+    ///
+    /// See [ErganiClient::prepare]
+    pub fn prepare_weekly_schedule(
+        &self,
+        company_weekly_schedules: &[CompanyWeeklySchedule],
+    ) -> Result<PreparedRequest, ErganiClientError> {
+        self.prepare(company_weekly_schedules)
+    }
+
+    /// Submits work card records (check-in, check-out) for employees to the Ergani API
+    ///
+    /// # Arguments:
+    /// * - `company_work_cards[Vec<CompanyWorkCard>]` - A Vec of CompanyWorkCard instances to be submitted
+    /// * - `auth_state` - The authentication state of the Ergani API
+    ///
+    /// # Returns:
+    /// * - `[SubmissionOutcome]` - The SubmissionResponse list parsed from the API response and the current authentication state
+    ///
+    /// # Errors:
+    /// * - `[ErganiClientError::Api]` - An error occurred while communicating with the Ergani API
+    /// * - `[ErganiClientError::AuthenticationFailed]` - Raised if there is an authentication error with the Ergani API
+    pub async fn submit_work_card(
+        &self,
+        company_work_cards: Vec<CompanyWorkCard>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self.submit(company_work_cards, auth_state).await
     }
 
     /// Submits overtime records for employees to the Ergani API
@@ -122,32 +456,17 @@ impl ErganiClient {
     /// * - `auth_state` - The authentication state of the Ergani API
     ///
     /// # Returns:
-    /// * - `[Vec<SubmissionResponse>]` - A list of SubmissionResponse that were parsed from the API response
+    /// * - `[SubmissionOutcome]` - The SubmissionResponse list parsed from the API response and the current authentication state
     ///
     /// # Errors:
-    /// * - `[APIError::General]` - An error occurred while communicating with the Ergani API
-    /// * - `[APIError::AuthenticationError]` - Raised if there is an authentication error with the Ergani API
+    /// * - `[ErganiClientError::Api]` - An error occurred while communicating with the Ergani API
+    /// * - `[ErganiClientError::AuthenticationFailed]` - Raised if there is an authentication error with the Ergani API
     pub async fn submit_overtime(
         &self,
         company_overtimes: Vec<CompanyOvertime>,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<Vec<SubmissionResponse>> {
-        let params = serde_json::to_value(company_overtimes)?;
-        let request_payload = json!({
-            "Overtimes": {
-                "Overtime": params
-            }
-        });
-
-        let payload = ErganiRequestPayload::builder()
-            .method(Method::POST)
-            .endpoint(OVERTIME_ENDPOINT.to_string())
-            .body(request_payload)
-            .build();
-        let response = self._request(&payload, &auth_state).await?;
-
-        self._extract_submission_result(response.response, auth_state)
-            .await
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self.submit(company_overtimes, auth_state).await
     }
 
     /// Submits schedule records that are updated on a daily basis for employees to the Ergani API
@@ -156,33 +475,17 @@ impl ErganiClient {
     /// * - `company_daily_schedules[Vec<CompanyDailySchedule>]` - A Vec of CompanyDailySchedule instances to be submitted
     /// * - `auth_state` - The authentication state of the Ergani API
     /// # Returns:
-    /// * - `[Vec<SubmissionResponse>]` - A list of SubmissionResponse that were parsed from the API response
+    /// * - `[SubmissionOutcome]` - The SubmissionResponse list parsed from the API response and the current authentication state
     ///
     /// # Errors:
-    /// * - `[APIError::General]` - An error occurred while communicating with the Ergani API
-    /// * - `[APIError::AuthenticationError]` - Raised if there is an authentication error with the Ergani API
+    /// * - `[ErganiClientError::Api]` - An error occurred while communicating with the Ergani API
+    /// * - `[ErganiClientError::AuthenticationFailed]` - Raised if there is an authentication error with the Ergani API
     pub async fn submit_daily_schedule(
         &self,
         company_daily_schedules: Vec<CompanyDailySchedule>,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<Vec<SubmissionResponse>> {
-        let params = serde_json::to_value(company_daily_schedules)?;
-        let request_payload = json!({
-            "WTOS": {
-                "WTO": params
-            }
-        });
-
-        let payload = ErganiRequestPayload::builder()
-            .method(Method::POST)
-            .endpoint(DAILY_SCHEDULE_ENDPOINT.to_string())
-            .body(request_payload)
-            .build();
-
-        let response = self._request(&payload, &auth_state).await?;
-
-        self._extract_submission_result(response.response, auth_state)
-            .await
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self.submit(company_daily_schedules, auth_state).await
     }
 
     /// Submits weekly schedule records for employees to the Ergani API
@@ -191,33 +494,88 @@ impl ErganiClient {
     /// * - `company_weekly_schedules[Vec<CompanyWeeklySchedule>]` - A Vec of CompanyWeeklySchedule instances to be submitted
     /// * - `auth_state` - The authentication state of the Ergani API
     /// # Returns:
-    /// * - `[Vec<SubmissionResponse>]` - A list of SubmissionResponse that were parsed from the API response
+    /// * - `[SubmissionOutcome]` - The SubmissionResponse list parsed from the API response and the current authentication state
     ///
     /// # Errors:
-    /// * - `[APIError::General]` - An error occurred while communicating with the Ergani API
-    /// * - `[APIError::AuthenticationError]` - Raised if there is an authentication error with the Ergani API
+    /// * - `[ErganiClientError::Api]` - An error occurred while communicating with the Ergani API
+    /// * - `[ErganiClientError::AuthenticationFailed]` - Raised if there is an authentication error with the Ergani API
     pub async fn submit_weekly_schedule(
         &self,
         company_weekly_schedules: Vec<CompanyWeeklySchedule>,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<Vec<SubmissionResponse>> {
-        let params = serde_json::to_value(company_weekly_schedules)?;
-        let request_payload = json!({
-            "WTOS": {
-                "WTO": params
-            }
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self.submit(company_weekly_schedules, auth_state).await
+    }
+
+    /// Checks that the credentials, base URL, TLS setup and clock allow working with the Ergani API
+    ///
+    /// Logs in or refreshes the given authentication state, then reads the submission types as a lightweight
+    /// authenticated request. The check stops at the first failed step and never returns an error: failures
+    /// are reported in the [HealthCheckReport].
+    ///
+    /// # Arguments:
+    /// * - `auth` - A [LoginPayload](crate::auth::login_payload::LoginPayload) to log in with, or an
+    ///   [ErganiAuthenticationState] to refresh
+    ///
+    /// # Returns:
+    /// * - `HealthCheckReport` - The latency and outcome of every step, the token expiry and the clock skew
+    pub async fn health_check(&self, auth: impl Into<HealthCheckAuth>) -> HealthCheckReport {
+        let authenticator = self.authenticator();
+        let mut steps = vec![];
+
+        let started_at = Instant::now();
+        let (step, auth_result) = match auth.into() {
+            HealthCheckAuth::Login(login_payload) => (
+                HealthCheckStep::Login,
+                authenticator.login(login_payload).await,
+            ),
+            HealthCheckAuth::Refresh(auth_state) => (
+                HealthCheckStep::Refresh,
+                authenticator.refresh(&auth_state).await,
+            ),
+        };
+        steps.push(HealthCheckStepReport {
+            step,
+            latency: started_at.elapsed(),
+            error: auth_result.as_ref().err().map(ToString::to_string),
         });
 
-        let payload = ErganiRequestPayload::builder()
-            .method(Method::POST)
-            .endpoint(WEEKLY_SCHEDULE_ENDPOINT.to_string())
-            .body(request_payload)
-            .build();
+        let mut auth_state = auth_result.ok();
 
-        let response = self._request(&payload, &auth_state).await?;
+        if let Some(current_auth_state) = auth_state.clone() {
+            let started_at = Instant::now();
+            let lookup_result = self.fetch_submissions(current_auth_state).await;
+            steps.push(HealthCheckStepReport {
+                step: HealthCheckStep::Lookup,
+                latency: started_at.elapsed(),
+                error: lookup_result.as_ref().err().map(ToString::to_string),
+            });
 
-        self._extract_submission_result(response.response, auth_state)
-            .await
+            if let Ok(fetch_response) = lookup_result {
+                auth_state = Some(fetch_response.auth_state().clone());
+            }
+        }
+
+        let report = HealthCheckReport {
+            environment: self.environment.clone(),
+            steps,
+            token_expires_at: auth_state
+                .as_ref()
+                .and_then(|auth_state| auth_state.token_info().expires_at().copied()),
+            clock_skew: self.clock_skew(),
+            auth_state,
+        };
+
+        match report.failed_step() {
+            Some(failed_step) => warn!(
+                environment = %self.environment,
+                step = %failed_step.step(),
+                "Ergani health check failed"
+            ),
+            None => info!(environment = %self.environment, "Ergani health check passed"),
+        }
+
+        report
     }
 
     /// Fetches the submissions from the Ergani API
@@ -230,7 +588,7 @@ impl ErganiClient {
     pub async fn fetch_submissions(
         &self,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<ErganiFetchResponse<LookupRoot>> {
+    ) -> Result<ErganiFetchResponse<LookupRoot>, ErganiClientError> {
         let payload = ErganiRequestPayload::builder()
             .method(Method::GET)
             .endpoint(LOOKUP_SUBMISSIONS_ENDPOINT.to_string())
@@ -239,8 +597,7 @@ impl ErganiClient {
 
         let response = self._request(&payload, &auth_state).await?;
 
-        self._extract_fetch_result(response.response, auth_state)
-            .await
+        self._extract_fetch_result(response)
     }
 
     /// Fetches the weekly schedule from the Ergani API
@@ -253,7 +610,7 @@ impl ErganiClient {
     pub async fn fetch_weekly_schedule(
         &self,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<ErganiFetchResponse<WeekScheduleResponseRoot>> {
+    ) -> Result<ErganiFetchResponse<WeekScheduleResponseRoot>, ErganiClientError> {
         let payload = ErganiRequestPayload::builder()
             .method(Method::GET)
             .endpoint(WEEKLY_SCHEDULE_ENDPOINT.to_string())
@@ -261,8 +618,7 @@ impl ErganiClient {
             .build();
         let response = self._request(&payload, &auth_state).await?;
 
-        self._extract_fetch_result(response.response, auth_state)
-            .await
+        self._extract_fetch_result(response)
     }
 
     /// Fetches the daily schedule from the Ergani API
@@ -275,7 +631,7 @@ impl ErganiClient {
     pub async fn fetch_daily_schedule(
         &self,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<ErganiFetchResponse<DayScheduleResponseRoot>> {
+    ) -> Result<ErganiFetchResponse<DayScheduleResponseRoot>, ErganiClientError> {
         let payload = ErganiRequestPayload::builder()
             .method(Method::GET)
             .endpoint(DAILY_SCHEDULE_ENDPOINT.to_string())
@@ -283,8 +639,7 @@ impl ErganiClient {
             .build();
         let response = self._request(&payload, &auth_state).await?;
 
-        self._extract_fetch_result(response.response, auth_state)
-            .await
+        self._extract_fetch_result(response)
     }
 
     /// Fetches the work cards from the Ergani API
@@ -297,7 +652,7 @@ impl ErganiClient {
     pub async fn fetch_work_cards(
         &self,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<ErganiFetchResponse<WorkCardResponseRoot>> {
+    ) -> Result<ErganiFetchResponse<WorkCardResponseRoot>, ErganiClientError> {
         let payload = ErganiRequestPayload::builder()
             .method(Method::GET)
             .endpoint(WORK_CARD_ENDPOINT.to_string())
@@ -305,8 +660,7 @@ impl ErganiClient {
             .build();
         let response = self._request(&payload, &auth_state).await?;
 
-        self._extract_fetch_result(response.response, auth_state)
-            .await
+        self._extract_fetch_result(response)
     }
 
     /// Fetches the overtime records from the Ergani API
@@ -319,7 +673,7 @@ impl ErganiClient {
     pub async fn fetch_overtimes(
         &self,
         auth_state: ErganiAuthenticationState,
-    ) -> Result<ErganiFetchResponse<OvertimeResponseRoot>> {
+    ) -> Result<ErganiFetchResponse<OvertimeResponseRoot>, ErganiClientError> {
         let payload = ErganiRequestPayload::builder()
             .method(Method::GET)
             .endpoint(OVERTIME_ENDPOINT.to_string())
@@ -327,8 +681,7 @@ impl ErganiClient {
             .build();
         let response = self._request(&payload, &auth_state).await?;
 
-        self._extract_fetch_result(response.response, auth_state)
-            .await
+        self._extract_fetch_result(response)
     }
 
     /// Sends a request to the specified endpoint using the given HTTP method and payload
@@ -341,26 +694,245 @@ impl ErganiClient {
     /// * - `ErganiRequestResponse` - The response from the Ergani API
     ///
     /// # Errors
-    /// * - `ApiError` - errors may occur for network-related errors
+    /// * - `[ErganiClientError::Network]` - Raised for network-related errors
+    /// * - `[ErganiClientError::Timeout]` - Raised if the request timed out
     async fn _request(
         &self,
         payload: &ErganiRequestPayload,
         auth_state: &ErganiAuthenticationState,
-    ) -> Result<ErganiRequestResponse> {
-        let url = format!("{}{}", self.base_url, payload.endpoint);
+    ) -> Result<ErganiRequestResponse, ErganiClientError> {
+        let span = info_span!(
+            "ergani_request",
+            environment = %self.environment,
+            method = %payload.method,
+            endpoint = %payload.endpoint,
+        );
+
+        async {
+            let auth_state = self._renew_expiring_auth_state(auth_state).await?;
+            let response = self._send(payload, &auth_state).await?;
+
+            self._handle_response(payload, response, &auth_state).await
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Sends the request described by the payload, authenticated with the given state,
+    /// retrying failed attempts according to the retry policy of the client
+    ///
+    /// # Arguments
+    /// * - `payload` - The payload of the request
+    /// * - `auth_state` - The authentication state of the Ergani API
+    async fn _send(
+        &self,
+        payload: &ErganiRequestPayload,
+        auth_state: &ErganiAuthenticationState,
+    ) -> Result<TransportResponse, ErganiClientError> {
+        let request = self._build_request(payload, auth_state.access_token().expose_secret())?;
+        let mut attempt = 1;
+
+        loop {
+            info!(
+                method = %payload.method,
+                endpoint = %payload.endpoint,
+                attempt,
+                "Sending request to the Ergani API"
+            );
+
+            let result = self._send_once(request.clone()).await;
+
+            let failure = match &result {
+                Ok(response) if response.status.is_success() => None,
+                Ok(response) => Some(AttemptFailure::Status(
+                    response.status,
+                    parse_retry_after(&response.headers),
+                )),
+                Err(error) => Some(match error.kind() {
+                    TransportErrorKind::Connect => AttemptFailure::Connect,
+                    TransportErrorKind::Timeout => AttemptFailure::Timeout,
+                    _ => AttemptFailure::Other,
+                }),
+            };
+
+            let retry_delay = failure.and_then(|failure| {
+                self.retry_policy
+                    .retry_delay(&payload.method, attempt, &failure)
+            });
+
+            match retry_delay {
+                Some(delay) => {
+                    warn!(
+                        method = %payload.method,
+                        endpoint = %payload.endpoint,
+                        attempt,
+                        delay_ms = delay.as_millis() as u64,
+                        "Request to the Ergani API failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                None => return result.map_err(ErganiClientError::from),
+            }
+        }
+    }
+
+    /// Sends a single attempt of a request
+    ///
+    /// # Arguments
+    /// * - `request` - The request to send
+    async fn _send_once(
+        &self,
+        request: TransportRequest,
+    ) -> Result<TransportResponse, TransportError> {
+        let response = self.transport.send(request).await?;
+        self.clock_skew.record(&response.headers);
+
+        Ok(response)
+    }
+
+    /// Builds the transport request described by the payload
+    ///
+    /// # Arguments
+    /// * - `payload` - The payload of the request
+    /// * - `access_token` - The access token to authenticate the request with
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::InvalidAccessToken]` - Raised if the access token cannot be sent in a header
+    fn _build_request(
+        &self,
+        payload: &ErganiRequestPayload,
+        access_token: &str,
+    ) -> Result<TransportRequest, ErganiClientError> {
+        let url = format!("{}{}", self.environment.base_url(), payload.endpoint);
+
+        TransportRequest::new(payload.method.clone(), url, payload.body.clone())
+            .with_bearer_auth(access_token)
+            .map_err(ErganiClientError::InvalidAccessToken)
+    }
+
+    /// Returns the AFMs of the employers the client acts for with the given authentication state: the configured
+    /// ones, the one given at login and the one in the claims of the access token
+    ///
+    /// # Arguments
+    /// * - `auth_state` - The authentication state documents would be submitted with
+    pub fn employer_tax_identification_numbers(
+        &self,
+        auth_state: &ErganiAuthenticationState,
+    ) -> Vec<Afm> {
+        let mut employer_tax_identification_numbers =
+            self.employer_tax_identification_numbers.clone();
+        let token_info = auth_state.token_info();
+
+        for afm in [
+            auth_state.employer_tax_identification_number(),
+            token_info.employer_tax_identification_number(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if !employer_tax_identification_numbers
+                .iter()
+                .any(|known| known == afm)
+            {
+                employer_tax_identification_numbers.push(afm.clone());
+            }
+        }
 
-        let mut request_builder = self
-            .http_client
-            .request(payload.method.clone(), url)
-            .bearer_auth(auth_state.access_token());
+        employer_tax_identification_numbers
+    }
 
-        if let Some(body) = payload.body.clone() {
-            request_builder = request_builder.json(&body);
+    /// Checks that every document is for an employer the client acts for
+    ///
+    /// Nothing is checked if the client allows any employer. Documents naming an employer are refused if the client
+    /// knows no employer to check them against.
+    ///
+    /// # Arguments
+    /// * - `documents` - The documents about to be submitted
+    /// * - `auth_state` - The authentication state the documents would be submitted with
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::EmployerMismatch]` - Raised for the first document of another employer
+    /// * - `[ErganiClientError::UnknownSessionEmployer]` - Raised for the first document naming an employer if the
+    ///   client knows no employer it acts for
+    fn _check_employer<D: ErganiDocument>(
+        &self,
+        documents: &[D],
+        auth_state: &ErganiAuthenticationState,
+    ) -> Result<(), ErganiClientError> {
+        if self.allow_any_employer {
+            return Ok(());
+        }
+
+        let expected = self.employer_tax_identification_numbers(auth_state);
+
+        match documents
+            .iter()
+            .filter_map(ErganiDocument::employer_tax_identification_number)
+            .find(|found| !expected.contains(found))
+        {
+            Some(found) if expected.is_empty() => {
+                Err(ErganiClientError::UnknownSessionEmployer(found.clone()))
+            }
+            Some(found) => Err(ErganiClientError::EmployerMismatch {
+                expected,
+                found: found.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the documents about to be submitted, unless the client was built with `validate_documents` off
+    ///
+    /// The paths of the issues start with the index of the document, e.g. `[0].card_details[3].employee_last_name`.
+    ///
+    /// # Arguments
+    /// * - `documents` - The documents about to be submitted
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::InvalidDocuments]` - Raised with every issue of every document
+    fn _validate_documents<D: ErganiDocument>(
+        &self,
+        documents: &[D],
+    ) -> Result<(), ErganiClientError> {
+        if !self.validate_documents {
+            return Ok(());
         }
 
-        let response = request_builder.send().await?;
+        let issues = documents
+            .iter()
+            .enumerate()
+            .flat_map(|(index, document)| {
+                let parent = format!("[{index}]");
+
+                document
+                    .validation_issues()
+                    .into_iter()
+                    .map(move |issue| issue.under(&parent))
+            })
+            .collect::<Vec<ValidationIssue>>();
+
+        match issues.is_empty() {
+            true => Ok(()),
+            false => Err(ErganiClientError::InvalidDocuments(ValidationErrors::new(
+                issues,
+            ))),
+        }
+    }
 
-        self._handle_response(payload, response, auth_state).await
+    /// Builds the payload of a submission of the documents
+    ///
+    /// # Arguments
+    /// * - `documents` - The documents to be submitted
+    fn _submission_payload<D: ErganiDocument>(
+        &self,
+        documents: &[D],
+    ) -> Result<ErganiRequestPayload, ErganiClientError> {
+        Ok(ErganiRequestPayload::builder()
+            .method(Method::POST)
+            .endpoint(D::ENDPOINT.to_string())
+            .body(to_envelope(documents)?)
+            .build())
     }
 
     /// Handles the HTTP response, raising exceptions for error status codes and returning the response for successful ones
@@ -373,137 +945,1000 @@ impl ErganiClient {
     /// * - `ErganiRequestResponse` - The response from the Ergani API
     ///
     /// # Errors:
-    /// * - `[APIError::General]` - An error occurred while communicating with the Ergani API
-    /// * - `[APIError::NotFound]` - Raised if the requested resource was not found
-    /// * - `[APIError::AuthenticationError]` - Raised if there is an authentication error with the Ergani API
+    /// * - `[ErganiClientError::Api]` - An error occurred while communicating with the Ergani API
+    /// * - `[ErganiClientError::NotFound]` - Raised if the requested resource was not found
+    /// * - `[ErganiClientError::AuthenticationFailed]` - Raised if there is an authentication error with the Ergani API
     async fn _handle_response(
         &self,
         payload: &ErganiRequestPayload,
-        response: Response,
+        response: TransportResponse,
         auth_state: &ErganiAuthenticationState,
-    ) -> Result<ErganiRequestResponse> {
-        let status = response.status();
+    ) -> Result<ErganiRequestResponse, ErganiClientError> {
+        let status = response.status;
 
-        /// Refresh the authentication token if the response is a 401 Unauthorized
+        // Refresh the authentication token if the response is a 401 Unauthorized
         if status == StatusCode::UNAUTHORIZED {
-            info!("Refreshing authentication token");
+            let refreshed_auth_state = self._renew(auth_state).await?;
 
-            let ergani_authenticator = ErganiAuthenticator::builder()
-                .base_url(self.base_url.clone())
-                .build();
+            let response = self._send(payload, &refreshed_auth_state).await?;
 
-            let refreshed_auth_state = ergani_authenticator.refresh(auth_state).await;
-
-            if refreshed_auth_state.is_err() {
-                error!("Failed to refresh authentication token");
-                bail!(refreshed_auth_state.err().unwrap())
+            if response.status == StatusCode::UNAUTHORIZED {
+                let ergani_error = ErganiError::from_response(response.status, response.body);
+                return Err(ErganiClientError::AuthenticationFailed(ergani_error));
             }
 
-            let url = format!("{}{}", self.base_url, payload.endpoint);
+            // Return the response from the Ergani API, with the new authentication state
+            return self._into_request_response(response, refreshed_auth_state);
+        }
+
+        self._into_request_response(response, auth_state.clone())
+    }
+
+    /// Renews the authentication state ahead of a request if its access token expires within the refresh margin,
+    /// according to the clock of the Ergani API
+    ///
+    /// If the renewal fails while the access token has not expired yet, the current state is used, so that the
+    /// request can still go through.
+    ///
+    /// # Arguments
+    /// * - `auth_state` - The authentication state the request would be sent with
+    async fn _renew_expiring_auth_state(
+        &self,
+        auth_state: &ErganiAuthenticationState,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        let server_now = self.server_now();
 
-            let refreshed_auth_state = refreshed_auth_state.unwrap();
+        if !auth_state.access_token_expires_within(self.refresh_margin, server_now) {
+            return Ok(auth_state.clone());
+        }
 
-            let mut request_builder = self
-                .http_client
-                .request(payload.method.clone(), url)
-                .bearer_auth(refreshed_auth_state.access_token());
+        info!("Access token expires soon, renewing it ahead of the request");
 
-            if let Some(body) = payload.body.clone() {
-                request_builder = request_builder.json(&body);
+        match self._renew(auth_state).await {
+            Ok(renewed_auth_state) => Ok(renewed_auth_state),
+            Err(error)
+                if !auth_state.access_token_expires_within(TimeDelta::zero(), server_now) =>
+            {
+                warn!(%error, "Failed to renew the access token ahead of its expiry, using it as is");
+                Ok(auth_state.clone())
             }
+            Err(error) => Err(error),
+        }
+    }
 
-            let response = request_builder.send().await?;
+    /// Renews the authentication state of a request through the renewal hook of the client if it has one,
+    /// e.g. the one of the session that owns the state, or on its own otherwise
+    ///
+    /// # Arguments
+    /// * - `auth_state` - The authentication state the request was sent with
+    async fn _renew(
+        &self,
+        auth_state: &ErganiAuthenticationState,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        match &self.auth_state_renewal {
+            Some(auth_state_renewal) => auth_state_renewal.renew(auth_state).await,
+            None => {
+                self._renew_auth_state(&self.authenticator(), auth_state)
+                    .await
+            }
+        }
+    }
 
-            if response.status().is_success() {
-                let auth_state = ErganiAuthenticationState::builder()
-                    .access_token(refreshed_auth_state.access_token().to_string())
-                    .access_token_expired(refreshed_auth_state.access_token_expired())
-                    .refresh_token(refreshed_auth_state.refresh_token().to_string())
-                    .refresh_token_expired(refreshed_auth_state.refresh_token_expired().to_utc())
-                    .build();
+    /// Renews an authentication state whose access token was rejected, by refreshing it or, once the
+    /// refresh token has expired, by logging in again with the credential provider of the client
+    ///
+    /// # Arguments
+    /// * - `authenticator` - The authenticator to refresh and log in with, e.g. the one of a session
+    /// * - `auth_state` - The rejected authentication state
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::AuthenticationFailed]` - Raised if the refresh or the login is rejected
+    /// * - `[ErganiClientError::RefreshTokenExpired]` - Raised if the refresh token has expired and there is no credential provider
+    /// * - `[ErganiClientError::ReloginRateLimited]` - Raised if the last attempt to log in again failed too recently
+    pub(crate) async fn _renew_auth_state(
+        &self,
+        authenticator: &ErganiAuthenticator,
+        auth_state: &ErganiAuthenticationState,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        let server_now = self.server_now();
 
-                /// Return the response from the Ergani API, with the new authentication state
-                let request_response = ErganiRequestResponse::builder()
-                    .maybe_response(Some(response))
-                    .auth_state(auth_state)
-                    .build();
+        if !auth_state.is_refresh_token_expired_at(server_now) {
+            info!("Refreshing authentication token");
 
-                return Ok(request_response);
+            match authenticator.refresh(auth_state).await {
+                Ok(refreshed_auth_state) => return Ok(refreshed_auth_state),
+                Err(error)
+                    if *auth_state.refresh_token_expired()
+                        > server_now + REFRESH_TOKEN_EXPIRY_TOLERANCE =>
+                {
+                    error!("Failed to refresh authentication token");
+                    return Err(error);
+                }
+                Err(_) => warn!("Failed to refresh authentication token close to its expiry"),
             }
-
-            let original_error = response.error_for_status_ref().unwrap_err();
-            let error_text = response.text().await?.to_string();
-            let ergani_error = ErganiError {
-                message: error_text,
-            };
-            bail!(APIError::AuthenticationFailed(original_error, ergani_error))
         }
 
-        if status == StatusCode::NOT_FOUND {
-            let original_error = response.error_for_status_ref().unwrap_err();
-            let ergani_error = response.json::<ErganiError>().await?;
-            bail!(APIError::NotFound(original_error, ergani_error))
-        }
+        self._relogin(authenticator).await
+    }
+
+    /// Logs in again with the credential provider of the client, at most once per re-login interval
+    ///
+    /// # Arguments
+    /// * - `authenticator` - The authenticator to log in with
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::RefreshTokenExpired]` - Raised if the client has no credential provider
+    /// * - `[ErganiClientError::ReloginRateLimited]` - Raised if the last attempt failed too recently
+    pub(crate) async fn _relogin(
+        &self,
+        authenticator: &ErganiAuthenticator,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        let Some(credential_provider) = &self.credential_provider else {
+            return Err(ErganiClientError::RefreshTokenExpired);
+        };
+
+        self.relogin_limiter
+            .relogin(|| async {
+                info!("Refresh token expired, logging in again");
+
+                let login_payload = credential_provider.credentials().await?;
+                let auth_state = authenticator
+                    .login(login_payload)
+                    .await
+                    .inspect_err(|_| error!("Failed to log in again"))?;
+
+                self.events.emit(ErganiEventKind::ReloginPerformed {
+                    auth_state: auth_state.clone(),
+                });
+
+                Ok(auth_state)
+            })
+            .await
+    }
+
+    /// Maps an HTTP response that does not need an authentication refresh to its result
+    /// # Arguments
+    /// * - `response` - The response object to map
+    /// * - `auth_state` - The authentication state the response was received with
+    ///
+    /// # Returns:
+    /// * - `ErganiRequestResponse` - The response from the Ergani API, without a body for 204 No Content
+    fn _into_request_response(
+        &self,
+        response: TransportResponse,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiRequestResponse, ErganiClientError> {
+        let status = response.status;
 
         if status == StatusCode::NO_CONTENT {
-            let request_response = ErganiRequestResponse::builder()
+            return Ok(ErganiRequestResponse::builder()
                 .maybe_response(None)
-                .auth_state(auth_state.clone())
-                .build();
-
-            return Ok(request_response);
+                .auth_state(auth_state)
+                .build());
         }
 
         if status.is_success() {
-            let request_response = ErganiRequestResponse::builder()
+            return Ok(ErganiRequestResponse::builder()
                 .maybe_response(Some(response))
-                .auth_state(auth_state.clone())
-                .build();
-
-            Ok(request_response)
-        } else {
-            let original_error = response.error_for_status_ref().unwrap_err();
-            let ergani_error = response.json::<ErganiError>().await?;
-            bail!(APIError::General(original_error, ergani_error))
+                .auth_state(auth_state)
+                .build());
+        }
+
+        let ergani_error = ErganiError::from_response(status, response.body);
+
+        match status {
+            StatusCode::UNAUTHORIZED => Err(ErganiClientError::AuthenticationFailed(ergani_error)),
+            StatusCode::NOT_FOUND => Err(ErganiClientError::NotFound(ergani_error)),
+            _ => Err(ErganiClientError::Api(ergani_error)),
         }
     }
 
     /// Extracts the submission result from the Ergani API response
     /// # Arguments:
-    /// * - `response` - The response object from the Ergani API
+    /// * - `request_response` - The response from the Ergani API, along with the authentication state used for it
     ///
     /// # Returns:
-    ///  * - `Vec<SubmissionResponse>` - A Vec of submission responses parsed from the API response
-    async fn _extract_submission_result(
+    ///  * - `SubmissionOutcome` - The submission responses parsed from the API response and the current authentication state
+    fn _extract_submission_result<T: DeserializeOwned>(
         &self,
-        response: Option<Response>,
-        auth_state: ErganiAuthenticationState,
-    ) -> Result<Vec<SubmissionResponse>> {
-        if response.is_none() {
-            return Ok(vec![]);
-        }
-
-        let response: Vec<SubmissionResponse> = response.unwrap().json().await?;
-
-        Ok(response)
+        request_response: ErganiRequestResponse,
+    ) -> Result<SubmissionOutcome<T>, ErganiClientError> {
+        let submissions: Vec<T> = match request_response.response {
+            Some(response) => deserialize_body(response.body)?,
+            None => vec![],
+        };
+
+        Ok(SubmissionOutcome::builder()
+            .submissions(submissions)
+            .auth_state(request_response.auth_state)
+            .environment(self.environment.clone())
+            .build())
     }
 
     /// Extracts the lookup result from the Ergani API response
     /// # Arguments:
-    /// * - `response` - The response object from the Ergani API
+    /// * - `request_response` - The response from the Ergani API, along with the authentication state used for it
     ///
     /// # Returns:
     ///  * - `T` - A Vec of T responses parsed from the API response
-    async fn _extract_fetch_result<T: DeserializeOwned>(
+    fn _extract_fetch_result<T: DeserializeOwned>(
         &self,
-        response: Option<Response>,
-        auth_state: ErganiAuthenticationState,
-    ) -> Result<ErganiFetchResponse<T>> {
-        let response: T = response.unwrap().json().await?;
+        request_response: ErganiRequestResponse,
+    ) -> Result<ErganiFetchResponse<T>, ErganiClientError> {
+        let response: Option<T> = match request_response.response {
+            Some(response) => Some(deserialize_body(response.body)?),
+            None => None,
+        };
 
         Ok(ErganiFetchResponse::builder()
-            .response(response)
-            .auth_state(auth_state)
+            .maybe_response(response)
+            .auth_state(request_response.auth_state)
             .build())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::credential_provider::StaticCredentialProvider;
+    use crate::endpoint::{
+        AUTHENTICATION_ENDPOINT, AUTHENTICATION_REFRESH_ENDPOINT, PRODUCTION_API_ENDPOINT,
+    };
+    use crate::environment::Url;
+    use crate::internal::tests::{
+        auth_state, client_with_responses, load_fixture_as_text, MockTransport,
+    };
+    use reqwest::header::{DATE, RETRY_AFTER};
+
+    #[test]
+    fn test_build_client_with_defaults() {
+        let client = ErganiClient::builder().build();
+
+        assert!(client.is_ok());
+        assert_eq!(client.unwrap().environment, ErganiEnvironment::Trial);
+    }
+
+    #[test]
+    fn test_build_client_with_custom_configuration() {
+        let client = ErganiClient::builder()
+            .environment(ErganiEnvironment::Production)
+            .allow_production(true)
+            .connect_timeout(Duration::from_secs(5))
+            .timeout(Duration::from_secs(15))
+            .proxy(Proxy::all("http://proxy.internal:3128").unwrap())
+            .user_agent("Payroll Service")
+            .build();
+
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_production_client_requires_opt_in() {
+        let client = ErganiClient::builder()
+            .environment(ErganiEnvironment::Production)
+            .build();
+
+        assert!(matches!(
+            client,
+            Err(ErganiClientError::ProductionNotAllowed)
+        ));
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_init_keeps_accepting_any_base_url() {
+        let production = ErganiClient::init(PRODUCTION_API_ENDPOINT.to_string());
+        let invalid = ErganiClient::init("not a url".to_string());
+
+        assert_eq!(production.environment(), &ErganiEnvironment::Production);
+        assert_eq!(invalid.environment(), &ErganiEnvironment::Trial);
+    }
+
+    #[test]
+    fn test_custom_url_with_a_query_is_refused() {
+        let url = Url::parse("https://host/api?x=1").unwrap();
+
+        let client = ErganiClient::builder()
+            .environment(ErganiEnvironment::Custom(url))
+            .build();
+
+        assert!(matches!(client, Err(ErganiClientError::InvalidBaseUrl(_))));
+    }
+
+    #[test]
+    fn test_custom_url_on_the_production_host_requires_opt_in() {
+        let environment =
+            ErganiEnvironment::from_base_url("HTTPS://eservices.yeka.gr/WebServicesAPI/api/v2")
+                .unwrap();
+        let client = ErganiClient::builder().environment(environment).build();
+
+        assert!(matches!(
+            client,
+            Err(ErganiClientError::ProductionNotAllowed)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_response_refreshes_token_and_resends_request() {
+        let (client, transport) = client_with_responses(vec![
+            TransportResponse::new(StatusCode::UNAUTHORIZED, ""),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("submission_response_fixture.json"),
+            ),
+        ]);
+
+        let outcome = client
+            .submit_work_card(vec![], auth_state().build())
+            .await
+            .unwrap();
+
+        assert_eq!(outcome.submissions().len(), 1);
+        assert_eq!(
+            outcome.auth_state().access_token().expose_secret(),
+            "refreshed-access-token"
+        );
+
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[1].url.ends_with(AUTHENTICATION_REFRESH_ENDPOINT));
+        assert_eq!(
+            requests[2].headers[AUTHORIZATION],
+            "Bearer refreshed-access-token"
+        );
+    }
+
+    /// Returns a client retrying up to 3 attempts without backoff, sending its requests to `transport`
+    fn client_with_retries(transport: Arc<MockTransport>) -> ErganiClient {
+        ErganiClient::builder()
+            .transport(transport)
+            .retry_policy(
+                RetryPolicy::builder()
+                    .max_attempts(3)
+                    .initial_backoff(Duration::ZERO)
+                    .jitter(false)
+                    .build(),
+            )
+            .build()
+            .unwrap()
+    }
+
+    fn timeout() -> TransportError {
+        TransportError::new(TransportErrorKind::Timeout, "timed out")
+    }
+
+    #[tokio::test]
+    async fn test_fetch_is_retried_on_service_unavailable() {
+        let transport = Arc::new(MockTransport::with_responses(vec![
+            TransportResponse::new(StatusCode::SERVICE_UNAVAILABLE, ""),
+            TransportResponse::new(StatusCode::NO_CONTENT, ""),
+        ]));
+        let client = client_with_retries(transport.clone());
+
+        let response = client.fetch_submissions(auth_state().build()).await;
+
+        assert!(response.is_ok());
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_stops_retrying_after_max_attempts() {
+        let transport = Arc::new(MockTransport::with_results(vec![
+            Err(timeout()),
+            Ok(TransportResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")),
+            Ok(TransportResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")),
+        ]));
+        let client = client_with_retries(transport.clone());
+
+        let error = client
+            .fetch_submissions(auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ErganiClientError::Api(_)));
+        assert_eq!(transport.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_submission_is_not_resent_after_timeout_or_server_error() {
+        let transport = Arc::new(MockTransport::with_results(vec![
+            Err(timeout()),
+            Ok(TransportResponse::new(StatusCode::SERVICE_UNAVAILABLE, "")),
+        ]));
+        let client = client_with_retries(transport.clone());
+
+        let timed_out = client
+            .submit_work_card(vec![], auth_state().build())
+            .await
+            .err()
+            .unwrap();
+        let unavailable = client
+            .submit_work_card(vec![], auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(timed_out, ErganiClientError::Timeout(_)));
+        assert!(matches!(unavailable, ErganiClientError::Api(_)));
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fetch_waits_for_retry_after() {
+        let mut too_many_requests = TransportResponse::new(StatusCode::TOO_MANY_REQUESTS, "");
+        too_many_requests
+            .headers
+            .insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        let transport = Arc::new(MockTransport::with_responses(vec![
+            too_many_requests,
+            TransportResponse::new(StatusCode::NO_CONTENT, ""),
+        ]));
+        let client = client_with_retries(transport.clone());
+
+        let started_at = tokio::time::Instant::now();
+        let response = client.fetch_submissions(auth_state().build()).await;
+
+        assert!(response.is_ok());
+        assert_eq!(started_at.elapsed(), Duration::from_secs(7));
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_fetch_gives_up_when_retry_after_exceeds_max_backoff() {
+        let mut too_many_requests = TransportResponse::new(StatusCode::TOO_MANY_REQUESTS, "");
+        too_many_requests
+            .headers
+            .insert(RETRY_AFTER, HeaderValue::from_static("3600"));
+        let transport = Arc::new(MockTransport::with_responses(vec![too_many_requests]));
+        let client = client_with_retries(transport.clone());
+
+        let started_at = tokio::time::Instant::now();
+        let response = client.fetch_submissions(auth_state().build()).await;
+
+        assert!(response.is_err());
+        assert_eq!(started_at.elapsed(), Duration::ZERO);
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_access_token_that_is_not_a_valid_header_is_refused_before_sending() {
+        let (client, transport) = client_with_responses(vec![]);
+
+        let error = client
+            .fetch_submissions(auth_state().access_token("access\ntoken").build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ErganiClientError::InvalidAccessToken(_)));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_response_after_refresh_fails_authentication() {
+        let (client, _) = client_with_responses(vec![
+            TransportResponse::new(StatusCode::UNAUTHORIZED, ""),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(StatusCode::UNAUTHORIZED, r#"{"message":"Invalid token"}"#),
+        ]);
+
+        let error = client
+            .submit_work_card(vec![], auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ErganiClientError::AuthenticationFailed(_)));
+        assert_eq!(error.ergani_error().unwrap().message(), "Invalid token");
+    }
+
+    #[tokio::test]
+    async fn test_failed_refresh_fails_authentication() {
+        let (client, transport) = client_with_responses(vec![
+            TransportResponse::new(StatusCode::UNAUTHORIZED, ""),
+            TransportResponse::new(StatusCode::BAD_REQUEST, "Refresh token expired"),
+        ]);
+
+        let error = client
+            .fetch_submissions(auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ErganiClientError::AuthenticationFailed(_)));
+        assert_eq!(transport.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_refresh_and_submission_events() {
+        let (client, _) = client_with_responses(vec![
+            TransportResponse::new(StatusCode::UNAUTHORIZED, ""),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("submission_response_fixture.json"),
+            ),
+        ]);
+        let mut events = client.subscribe();
+
+        client
+            .submit_work_card(vec![], auth_state().build())
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            events.try_recv().unwrap().kind(),
+            ErganiEventKind::TokenRefreshed { auth_state }
+                if auth_state.access_token().expose_secret() == "refreshed-access-token"
+        ));
+        assert!(matches!(
+            events.try_recv().unwrap().kind(),
+            ErganiEventKind::SubmissionAccepted { endpoint, submissions: 1, .. }
+                if endpoint == WORK_CARD_ENDPOINT
+        ));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_subscribers_receive_refresh_failures() {
+        let (client, _) = client_with_responses(vec![
+            TransportResponse::new(StatusCode::UNAUTHORIZED, ""),
+            TransportResponse::new(StatusCode::BAD_REQUEST, "Refresh token expired"),
+        ]);
+        let mut events = client.subscribe();
+
+        client
+            .fetch_submissions(auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        let event = events.try_recv().unwrap();
+        assert!(matches!(
+            event.kind(),
+            ErganiEventKind::RefreshFailed { error } if error.contains("Refresh token expired")
+        ));
+        assert!(*event.occurred_at() <= Utc::now());
+    }
+
+    #[tokio::test]
+    async fn test_access_token_is_refreshed_ahead_of_expiry_on_server_clock() {
+        let mut fetch_response = TransportResponse::new(StatusCode::NO_CONTENT, "");
+        fetch_response.headers.insert(
+            reqwest::header::DATE,
+            HeaderValue::from_str(&(Utc::now() + TimeDelta::hours(1)).to_rfc2822()).unwrap(),
+        );
+        let (client, transport) = client_with_responses(vec![
+            fetch_response,
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(StatusCode::NO_CONTENT, ""),
+        ]);
+        let auth_state = auth_state()
+            .access_token_expires_at(Utc::now() + TimeDelta::minutes(30))
+            .build();
+
+        let outcome = client.fetch_submissions(auth_state).await.unwrap();
+        assert!(client.clock_skew().unwrap() > TimeDelta::minutes(59));
+        let outcome = client
+            .fetch_submissions(outcome.auth_state().clone())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome.auth_state().access_token().expose_secret(),
+            "refreshed-access-token"
+        );
+        let requests = transport.requests();
+        assert!(requests[1].url.ends_with(AUTHENTICATION_REFRESH_ENDPOINT));
+        assert_eq!(
+            requests[2].headers[AUTHORIZATION],
+            "Bearer refreshed-access-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_every_step() {
+        let mut authentication_response = TransportResponse::new(
+            StatusCode::OK,
+            load_fixture_as_text("authentication_response_fixture.json"),
+        );
+        authentication_response.headers.insert(
+            reqwest::header::DATE,
+            HeaderValue::from_str(&(Utc::now() - TimeDelta::minutes(5)).to_rfc2822()).unwrap(),
+        );
+        let (client, transport) = client_with_responses(vec![
+            authentication_response,
+            TransportResponse::new(StatusCode::NO_CONTENT, ""),
+        ]);
+
+        let report = client
+            .health_check(
+                login_payload::LoginPayload::builder()
+                    .username("username".to_string())
+                    .password("password".to_string())
+                    .build(),
+            )
+            .await;
+
+        assert!(report.is_healthy());
+        let steps: Vec<HealthCheckStep> = report.steps().iter().map(|step| step.step()).collect();
+        assert_eq!(steps, vec![HealthCheckStep::Login, HealthCheckStep::Lookup]);
+        assert_eq!(report.token_expires_at().unwrap().timestamp(), 4102444800);
+        assert!(report.clock_skew().unwrap() < TimeDelta::minutes(-4));
+        assert!(transport.requests()[1]
+            .url
+            .ends_with(LOOKUP_SUBMISSIONS_ENDPOINT));
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_failed_refresh() {
+        let (client, transport) = client_with_responses(vec![TransportResponse::new(
+            StatusCode::UNAUTHORIZED,
+            "Invalid refresh token",
+        )]);
+
+        let report = client.health_check(auth_state().build()).await;
+
+        let failed_step = report.failed_step().unwrap();
+        assert_eq!(failed_step.step(), HealthCheckStep::Refresh);
+        assert!(failed_step
+            .error()
+            .unwrap()
+            .contains("Invalid refresh token"));
+        assert_eq!(report.steps().len(), 1);
+        assert!(report.auth_state().is_none());
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    fn company_work_card(employer_tax_identification_number: &str) -> CompanyWorkCard {
+        CompanyWorkCard {
+            employer_tax_identification_number: employer_tax_identification_number.parse().unwrap(),
+            business_branch_number: 1,
+            comments: None,
+            card_details: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_employers_the_client_does_not_act_for() {
+        let transport = Arc::new(MockTransport::with_responses(vec![TransportResponse::new(
+            StatusCode::NO_CONTENT,
+            "",
+        )]));
+        let client = ErganiClient::builder()
+            .transport(transport.clone())
+            .employer_tax_identification_numbers(vec![
+                "123456783".parse().unwrap(),
+                "987654324".parse().unwrap(),
+            ])
+            .build()
+            .unwrap();
+
+        client
+            .submit_work_card(vec![company_work_card("987654324")], auth_state().build())
+            .await
+            .unwrap();
+        let error = client
+            .submit_work_card(vec![company_work_card("111111114")], auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            error,
+            ErganiClientError::EmployerMismatch { expected, found }
+                if expected == ["123456783", "987654324"] && found == "111111114"
+        ));
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_employers_when_the_client_knows_none() {
+        let (client, transport) = client_with_responses(vec![]);
+
+        let error = client
+            .submit_work_card(vec![company_work_card("123456783")], auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            error,
+            ErganiClientError::UnknownSessionEmployer(found) if found == "123456783"
+        ));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_allows_any_employer_when_overridden() {
+        let transport = Arc::new(MockTransport::with_responses(vec![TransportResponse::new(
+            StatusCode::NO_CONTENT,
+            "",
+        )]));
+        let client = ErganiClient::builder()
+            .transport(transport.clone())
+            .allow_any_employer(true)
+            .build()
+            .unwrap();
+        let auth_state = auth_state()
+            .employer_tax_identification_number("123456783".parse().unwrap())
+            .build();
+
+        client
+            .submit_work_card(vec![company_work_card("987654324")], auth_state)
+            .await
+            .unwrap();
+
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_refuses_invalid_documents_unless_validation_is_off() {
+        let (client, transport) = client_with_responses(vec![]);
+        let mut invalid_work_card = company_work_card("123456783");
+        invalid_work_card.business_branch_number = -1;
+        let documents = vec![company_work_card("123456783"), invalid_work_card];
+        let auth_state = auth_state()
+            .employer_tax_identification_number("123456783".parse().unwrap())
+            .build();
+
+        let error = client
+            .submit_work_card(documents.clone(), auth_state.clone())
+            .await
+            .err()
+            .unwrap();
+
+        assert_eq!(
+            error.to_string(),
+            "Invalid documents: [1].business_branch_number: must not be negative, found -1"
+        );
+        assert!(transport.requests().is_empty());
+
+        let transport = Arc::new(MockTransport::with_responses(vec![TransportResponse::new(
+            StatusCode::NO_CONTENT,
+            "",
+        )]));
+        let client = ErganiClient::builder()
+            .transport(transport.clone())
+            .validate_documents(false)
+            .build()
+            .unwrap();
+
+        client
+            .submit_work_card(documents, auth_state)
+            .await
+            .unwrap();
+
+        assert_eq!(transport.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_non_json_error_response_maps_to_api_error() {
+        let body = "<html>Internal Server Error</html>";
+        let (client, _) = client_with_responses(vec![TransportResponse::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            body,
+        )]);
+
+        let error = client
+            .fetch_submissions(auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ErganiClientError::Api(_)));
+        assert_eq!(error.status(), Some(StatusCode::INTERNAL_SERVER_ERROR));
+        assert_eq!(error.ergani_error().unwrap().body(), body);
+    }
+
+    #[tokio::test]
+    async fn test_not_found_response_maps_to_not_found_error() {
+        let (client, _) =
+            client_with_responses(vec![TransportResponse::new(StatusCode::NOT_FOUND, "")]);
+
+        let error = client
+            .fetch_work_cards(auth_state().build())
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ErganiClientError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_no_content_submission_returns_no_submissions() {
+        let (client, _) =
+            client_with_responses(vec![TransportResponse::new(StatusCode::NO_CONTENT, "")]);
+
+        let outcome = client
+            .submit_overtime(vec![], auth_state().build())
+            .await
+            .unwrap();
+
+        assert!(outcome.submissions().is_empty());
+        assert_eq!(
+            outcome.auth_state().access_token().expose_secret(),
+            "access-token"
+        );
+        assert_eq!(outcome.environment(), &ErganiEnvironment::Trial);
+    }
+
+    #[tokio::test]
+    async fn test_submit_sends_document_envelope_to_document_endpoint() {
+        let (client, transport) =
+            client_with_responses(vec![TransportResponse::new(StatusCode::NO_CONTENT, "")]);
+        let company_work_card = CompanyWorkCard {
+            employer_tax_identification_number: "123456783".parse().unwrap(),
+            business_branch_number: 0,
+            comments: None,
+            card_details: vec![],
+        };
+
+        client
+            .submit(
+                vec![company_work_card],
+                auth_state()
+                    .employer_tax_identification_number("123456783".parse().unwrap())
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let request = &transport.requests()[0];
+        assert_eq!(request.method, Method::POST);
+        assert!(request.url.ends_with(WORK_CARD_ENDPOINT));
+        assert_eq!(
+            request.body,
+            Some(json!({"Cards": {"Card": [{
+                "f_afm_ergodoti": "123456783",
+                "f_aa": "0",
+                "f_comments": "",
+                "Details": {"CardDetails": []}
+            }]}}))
+        );
+    }
+
+    #[test]
+    fn test_prepare_builds_request_without_sending_it() {
+        let (client, transport) = client_with_responses(vec![]);
+        let company_work_card = CompanyWorkCard {
+            employer_tax_identification_number: "123456783".parse().unwrap(),
+            business_branch_number: 0,
+            comments: None,
+            card_details: vec![],
+        };
+
+        let prepared = client.prepare_work_card(&[company_work_card]).unwrap();
+
+        assert_eq!(prepared.method(), Method::POST);
+        assert_eq!(
+            prepared.url(),
+            format!("{TRIAL_API_ENDPOINT}{WORK_CARD_ENDPOINT}")
+        );
+        assert_eq!(prepared.headers()[AUTHORIZATION], "Bearer ***");
+        assert_eq!(
+            format!("{:?}", prepared.headers()[AUTHORIZATION]),
+            "\"Bearer ***\""
+        );
+        assert_eq!(
+            prepared.body()["Cards"]["Card"][0]["f_afm_ergodoti"],
+            "123456783"
+        );
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_expired_refresh_token_logs_in_again_with_credential_provider() {
+        let transport = Arc::new(MockTransport::with_responses(vec![
+            TransportResponse::new(StatusCode::UNAUTHORIZED, ""),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(StatusCode::NO_CONTENT, ""),
+        ]));
+        let credential_provider = StaticCredentialProvider::new(
+            login_payload::LoginPayload::builder()
+                .username("username".to_string())
+                .password("password".to_string())
+                .build(),
+        );
+        let client = ErganiClient::builder()
+            .transport(transport.clone())
+            .credential_provider(Arc::new(credential_provider))
+            .build()
+            .unwrap();
+
+        let outcome = client
+            .submit_work_card(
+                vec![],
+                auth_state()
+                    .refresh_token_expires_at(Utc::now() - TimeDelta::hours(1))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            outcome.auth_state().access_token().expose_secret(),
+            "refreshed-access-token"
+        );
+        let requests = transport.requests();
+        assert!(requests[1].url.ends_with(AUTHENTICATION_ENDPOINT));
+        assert_eq!(requests[1].body.as_ref().unwrap()["Username"], "username");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_expired_by_the_server_clock_logs_in_again() {
+        let mut unauthorized = TransportResponse::new(StatusCode::UNAUTHORIZED, "");
+        unauthorized.headers.insert(
+            DATE,
+            HeaderValue::from_str(&(Utc::now() + TimeDelta::hours(2)).to_rfc2822()).unwrap(),
+        );
+        let transport = Arc::new(MockTransport::with_responses(vec![
+            unauthorized,
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(StatusCode::NO_CONTENT, ""),
+        ]));
+        let credential_provider = StaticCredentialProvider::new(
+            login_payload::LoginPayload::builder()
+                .username("username".to_string())
+                .password("password".to_string())
+                .build(),
+        );
+        let client = ErganiClient::builder()
+            .transport(transport.clone())
+            .credential_provider(Arc::new(credential_provider))
+            .build()
+            .unwrap();
+
+        client
+            .fetch_submissions(
+                auth_state()
+                    .refresh_token_expires_at(Utc::now() + TimeDelta::hours(1))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let requests = transport.requests();
+        assert!(requests[1].url.ends_with(AUTHENTICATION_ENDPOINT));
+        assert_eq!(requests[1].body.as_ref().unwrap()["Username"], "username");
+    }
+
+    #[tokio::test]
+    async fn test_expired_refresh_token_fails_without_credential_provider() {
+        let (client, transport) =
+            client_with_responses(vec![TransportResponse::new(StatusCode::UNAUTHORIZED, "")]);
+
+        let error = client
+            .fetch_submissions(
+                auth_state()
+                    .refresh_token_expires_at(Utc::now() - TimeDelta::hours(1))
+                    .build(),
+            )
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(error, ErganiClientError::RefreshTokenExpired));
+        assert_eq!(transport.requests().len(), 1);
+    }
+}
@@ -0,0 +1,604 @@
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, RwLock};
+
+use async_trait::async_trait;
+use bon::{bon, Builder};
+use tokio::sync::{OnceCell, OwnedSemaphorePermit, Semaphore};
+
+use crate::api_error::ErganiClientError;
+use crate::auth::authenticator::ErganiAuthenticationState;
+use crate::auth::credential_provider::CredentialProvider;
+use crate::auth::login_payload::LoginPayload;
+use crate::auth::token_store::TokenStore;
+use crate::client::{ErganiClient, SubmissionResponse};
+use crate::ergani_document::ErganiDocument;
+use crate::models::company::company_daily_schedule::CompanyDailySchedule;
+use crate::models::company::company_overtime::CompanyOvertime;
+use crate::models::company::company_weekly_schedule::CompanyWeeklySchedule;
+use crate::models::company::company_work_card::CompanyWorkCard;
+use crate::models::types::afm::Afm;
+use crate::responses::day_schedule_response::DayScheduleResponseRoot;
+use crate::responses::lookup_response::LookupRoot;
+use crate::responses::overtime_response::OvertimeResponseRoot;
+use crate::responses::week_schedule_response::WeekScheduleResponseRoot;
+use crate::responses::work_card_response::WorkCardResponseRoot;
+use crate::session::ErganiSession;
+
+/// The default number of concurrent calls allowed per employer
+const DEFAULT_MAX_CONCURRENT_CALLS: usize = 4;
+
+/// An employer served by an [ErganiClientPool]
+/// * - `employer_tax_identification_number` - The AFM of the employer, which calls are routed by
+/// * - `credential_provider` - The credentials to log in with for this employer
+/// * - `token_store` - Where the authentication state of this employer is kept between process restarts
+/// * - `max_concurrent_calls` - How many calls for this employer may run at once. Defaults to the limit of the pool.
+#[derive(Builder, Clone)]
+pub struct PoolEmployer {
+    employer_tax_identification_number: Afm,
+    credential_provider: Arc<dyn CredentialProvider>,
+    token_store: Option<Arc<dyn TokenStore>>,
+    max_concurrent_calls: Option<usize>,
+}
+
+impl PoolEmployer {
+    pub fn employer_tax_identification_number(&self) -> &Afm {
+        &self.employer_tax_identification_number
+    }
+}
+
+/// Clients for many employers, keyed by employer AFM
+///
+/// Every employer has its own credentials, session and token store, while all of them share the transport,
+/// and so the HTTP connection pool, of one client. Sessions are created on the first call for an employer.
+/// Calls for one employer are limited to its `max_concurrent_calls`, so that one busy employer cannot
+/// exhaust the connections of the others. Clones share the same entries.
+#[derive(Clone)]
+pub struct ErganiClientPool {
+    inner: Arc<ErganiClientPoolInner>,
+}
+
+struct ErganiClientPoolInner {
+    client: ErganiClient,
+    max_concurrent_calls: usize,
+    entries: RwLock<HashMap<Afm, Arc<PoolEntry>>>,
+}
+
+/// The client, lazily created session and concurrency limit of one employer
+struct PoolEntry {
+    employer: PoolEmployer,
+    client: ErganiClient,
+    credential_provider: Arc<dyn CredentialProvider>,
+    session: OnceCell<ErganiSession>,
+    permits: Arc<Semaphore>,
+}
+
+/// A session of an [ErganiClientPool] entry, holding one of its concurrent call permits until dropped
+///
+/// The session itself is not exposed, so that no call can be made for the employer without a permit.
+pub struct PooledSession {
+    session: ErganiSession,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl PooledSession {
+    /// Returns the current authentication state of the session
+    pub async fn auth_state(&self) -> ErganiAuthenticationState {
+        self.session.auth_state().await
+    }
+
+    /// Refreshes the authentication state of the session
+    ///
+    /// See [ErganiSession::refresh]
+    pub async fn refresh(&self) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        self.session.refresh().await
+    }
+
+    /// Returns the AFM of the employer the session acts for, if it was given at login
+    pub async fn employer_tax_identification_number(&self) -> Option<Afm> {
+        self.session.employer_tax_identification_number().await
+    }
+
+    /// Submits documents of any [ErganiDocument] type to the Ergani API
+    ///
+    /// See [ErganiSession::submit]
+    pub async fn submit<D: ErganiDocument>(
+        &self,
+        documents: Vec<D>,
+    ) -> Result<Vec<D::Response>, ErganiClientError> {
+        self.session.submit(documents).await
+    }
+
+    /// Submits work cards to the Ergani API
+    ///
+    /// See [ErganiSession::submit_work_card]
+    pub async fn submit_work_card(
+        &self,
+        company_work_cards: Vec<CompanyWorkCard>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.session.submit_work_card(company_work_cards).await
+    }
+
+    /// Submits overtime records to the Ergani API
+    ///
+    /// See [ErganiSession::submit_overtime]
+    pub async fn submit_overtime(
+        &self,
+        company_overtimes: Vec<CompanyOvertime>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.session.submit_overtime(company_overtimes).await
+    }
+
+    /// Submits daily schedules to the Ergani API
+    ///
+    /// See [ErganiSession::submit_daily_schedule]
+    pub async fn submit_daily_schedule(
+        &self,
+        company_daily_schedules: Vec<CompanyDailySchedule>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.session
+            .submit_daily_schedule(company_daily_schedules)
+            .await
+    }
+
+    /// Submits weekly schedules to the Ergani API
+    ///
+    /// See [ErganiSession::submit_weekly_schedule]
+    pub async fn submit_weekly_schedule(
+        &self,
+        company_weekly_schedules: Vec<CompanyWeeklySchedule>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.session
+            .submit_weekly_schedule(company_weekly_schedules)
+            .await
+    }
+
+    /// Fetches the submissions from the Ergani API
+    ///
+    /// See [ErganiSession::fetch_submissions]
+    pub async fn fetch_submissions(&self) -> Result<Option<LookupRoot>, ErganiClientError> {
+        self.session.fetch_submissions().await
+    }
+
+    /// Fetches the weekly schedule from the Ergani API
+    ///
+    /// See [ErganiSession::fetch_weekly_schedule]
+    pub async fn fetch_weekly_schedule(
+        &self,
+    ) -> Result<Option<WeekScheduleResponseRoot>, ErganiClientError> {
+        self.session.fetch_weekly_schedule().await
+    }
+
+    /// Fetches the daily schedule from the Ergani API
+    ///
+    /// See [ErganiSession::fetch_daily_schedule]
+    pub async fn fetch_daily_schedule(
+        &self,
+    ) -> Result<Option<DayScheduleResponseRoot>, ErganiClientError> {
+        self.session.fetch_daily_schedule().await
+    }
+
+    /// Fetches the work cards from the Ergani API
+    ///
+    /// See [ErganiSession::fetch_work_cards]
+    pub async fn fetch_work_cards(
+        &self,
+    ) -> Result<Option<WorkCardResponseRoot>, ErganiClientError> {
+        self.session.fetch_work_cards().await
+    }
+
+    /// Fetches the overtime records from the Ergani API
+    ///
+    /// See [ErganiSession::fetch_overtimes]
+    pub async fn fetch_overtimes(&self) -> Result<Option<OvertimeResponseRoot>, ErganiClientError> {
+        self.session.fetch_overtimes().await
+    }
+}
+
+#[bon]
+impl ErganiClientPool {
+    /// Creates a pool of clients for many employers
+    ///
+    /// # Arguments
+    /// * - `client` - The client whose transport, environment and configuration every employer shares
+    /// * - `employers` - The employers served by the pool. More can be added with [ErganiClientPool::insert].
+    /// * - `max_concurrent_calls` - How many calls for one employer may run at once. Defaults to 4.
+    #[builder]
+    pub fn new(
+        client: ErganiClient,
+        #[builder(default)] employers: Vec<PoolEmployer>,
+        #[builder(default = DEFAULT_MAX_CONCURRENT_CALLS)] max_concurrent_calls: usize,
+    ) -> Self {
+        let pool = ErganiClientPool {
+            inner: Arc::new(ErganiClientPoolInner {
+                client,
+                max_concurrent_calls,
+                entries: RwLock::new(HashMap::new()),
+            }),
+        };
+
+        for employer in employers {
+            pool.insert(employer);
+        }
+
+        pool
+    }
+
+    /// Adds an employer to the pool, replacing any entry with the same AFM
+    ///
+    /// # Arguments
+    /// * - `employer` - The employer to add
+    pub fn insert(&self, employer: PoolEmployer) {
+        let credential_provider: Arc<dyn CredentialProvider> =
+            Arc::new(EmployerCredentialProvider {
+                employer_tax_identification_number: employer
+                    .employer_tax_identification_number
+                    .clone(),
+                credential_provider: employer.credential_provider.clone(),
+            });
+        let max_concurrent_calls = employer
+            .max_concurrent_calls
+            .unwrap_or(self.inner.max_concurrent_calls)
+            .max(1);
+
+        let entry = PoolEntry {
+            client: self
+                .inner
+                .client
+                .with_credential_provider(credential_provider.clone()),
+            credential_provider,
+            session: OnceCell::new(),
+            permits: Arc::new(Semaphore::new(max_concurrent_calls)),
+            employer,
+        };
+
+        self.inner.entries.write().unwrap().insert(
+            entry.employer.employer_tax_identification_number.clone(),
+            Arc::new(entry),
+        );
+    }
+
+    /// Removes an employer from the pool, returning whether it was in it
+    ///
+    /// Calls already running for the employer finish with its session.
+    pub fn remove(&self, employer_tax_identification_number: &Afm) -> bool {
+        self.inner
+            .entries
+            .write()
+            .unwrap()
+            .remove(employer_tax_identification_number)
+            .is_some()
+    }
+
+    /// Returns the AFMs of the employers in the pool, sorted
+    pub fn employers(&self) -> Vec<Afm> {
+        let mut employers: Vec<Afm> = self.inner.entries.read().unwrap().keys().cloned().collect();
+        employers.sort();
+        employers
+    }
+
+    /// Returns the session of an employer, waiting for one of its concurrent call permits
+    ///
+    /// The session is created on the first call, from the token store of the employer if it holds a valid
+    /// authentication state, or else by logging in with its credential provider. The permit is released when
+    /// the returned session is dropped.
+    ///
+    /// # Arguments
+    /// * - `employer_tax_identification_number` - The AFM of the employer
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::UnknownEmployer]` - Raised if the employer is not in the pool
+    pub async fn session(
+        &self,
+        employer_tax_identification_number: &Afm,
+    ) -> Result<PooledSession, ErganiClientError> {
+        let entry = self._entry(employer_tax_identification_number)?;
+
+        let permit = entry
+            .permits
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore of a pool entry is never closed");
+
+        let session = entry
+            .session
+            .get_or_try_init(|| entry._create_session())
+            .await?
+            .clone();
+
+        Ok(PooledSession {
+            session,
+            _permit: permit,
+        })
+    }
+
+    /// Submits documents for one employer
+    ///
+    /// # Arguments
+    /// * - `employer_tax_identification_number` - The AFM of the employer the documents are submitted for
+    /// * - `documents` - The documents to be submitted
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::UnknownEmployer]` - Raised if the employer is not in the pool
+    /// * - `[ErganiClientError::EmployerMismatch]` - Raised if a document names another employer
+    pub async fn submit_for<D: ErganiDocument>(
+        &self,
+        employer_tax_identification_number: &Afm,
+        documents: Vec<D>,
+    ) -> Result<Vec<D::Response>, ErganiClientError> {
+        self.session(employer_tax_identification_number)
+            .await?
+            .submit(documents)
+            .await
+    }
+
+    /// Submits documents that name their employer, each through the entry of its employer
+    ///
+    /// Every document is routed before anything is sent, so a document without an employer AFM or for an
+    /// employer outside the pool fails the whole call. The employers are then submitted for one after the
+    /// other, and the result of each is returned, so a failure for one employer does not hide the
+    /// submissions accepted for the others.
+    ///
+    /// # Arguments
+    /// * - `documents` - The documents to be submitted
+    ///
+    /// # Returns
+    /// * - `BTreeMap` - The submission result of every employer, keyed by employer AFM
+    ///
+    /// # Errors
+    /// * - `[ErganiClientError::MissingEmployer]` - Raised if a document does not name its employer
+    /// * - `[ErganiClientError::UnknownEmployer]` - Raised if an employer is not in the pool
+    pub async fn submit<D: ErganiDocument>(
+        &self,
+        documents: Vec<D>,
+    ) -> Result<BTreeMap<Afm, Result<Vec<D::Response>, ErganiClientError>>, ErganiClientError> {
+        let mut documents_by_employer: BTreeMap<Afm, Vec<D>> = BTreeMap::new();
+
+        for document in documents {
+            let employer_tax_identification_number = document
+                .employer_tax_identification_number()
+                .ok_or(ErganiClientError::MissingEmployer)?
+                .clone();
+
+            self._entry(&employer_tax_identification_number)?;
+
+            documents_by_employer
+                .entry(employer_tax_identification_number)
+                .or_default()
+                .push(document);
+        }
+
+        let mut results = BTreeMap::new();
+        for (employer_tax_identification_number, documents) in documents_by_employer {
+            let result = self
+                .submit_for(&employer_tax_identification_number, documents)
+                .await;
+            results.insert(employer_tax_identification_number, result);
+        }
+
+        Ok(results)
+    }
+
+    /// Submits work cards, each through the entry of the employer it names
+    ///
+    /// See [ErganiClientPool::submit]
+    pub async fn submit_work_card(
+        &self,
+        company_work_cards: Vec<CompanyWorkCard>,
+    ) -> Result<BTreeMap<Afm, Result<Vec<SubmissionResponse>, ErganiClientError>>, ErganiClientError>
+    {
+        self.submit(company_work_cards).await
+    }
+
+    fn _entry(
+        &self,
+        employer_tax_identification_number: &Afm,
+    ) -> Result<Arc<PoolEntry>, ErganiClientError> {
+        self.inner
+            .entries
+            .read()
+            .unwrap()
+            .get(employer_tax_identification_number)
+            .cloned()
+            .ok_or_else(|| {
+                ErganiClientError::UnknownEmployer(employer_tax_identification_number.clone())
+            })
+    }
+}
+
+impl PoolEntry {
+    /// Creates the session of the employer, restoring it from the token store if possible
+    async fn _create_session(&self) -> Result<ErganiSession, ErganiClientError> {
+        let login_payload = self.credential_provider.credentials().await?;
+
+        match &self.employer.token_store {
+            Some(token_store) => {
+                ErganiSession::restore_or_login(
+                    self.client.authenticator(),
+                    self.client.clone(),
+                    token_store.clone(),
+                    login_payload,
+                )
+                .await
+            }
+            None => {
+                ErganiSession::login(
+                    self.client.authenticator(),
+                    self.client.clone(),
+                    login_payload,
+                )
+                .await
+            }
+        }
+    }
+}
+
+/// A [CredentialProvider] that logs in acting for one employer, so that its session refuses documents
+/// of other employers even after logging in again
+struct EmployerCredentialProvider {
+    employer_tax_identification_number: Afm,
+    credential_provider: Arc<dyn CredentialProvider>,
+}
+
+#[async_trait]
+impl CredentialProvider for EmployerCredentialProvider {
+    async fn credentials(&self) -> Result<LoginPayload, ErganiClientError> {
+        Ok(self
+            .credential_provider
+            .credentials()
+            .await?
+            .or_employer_tax_identification_number(&self.employer_tax_identification_number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::credential_provider::StaticCredentialProvider;
+    use crate::endpoint::{AUTHENTICATION_ENDPOINT, WORK_CARD_ENDPOINT};
+    use crate::internal::tests::{client_with_responses, load_fixture_as_text};
+    use crate::transport::TransportResponse;
+    use reqwest::StatusCode;
+
+    fn afm(afm: &str) -> Afm {
+        afm.parse().unwrap()
+    }
+
+    fn employer(afm: &str, username: &str) -> PoolEmployer {
+        let credentials = LoginPayload::builder()
+            .username(username.to_string())
+            .password("password".to_string())
+            .build();
+
+        PoolEmployer::builder()
+            .employer_tax_identification_number(self::afm(afm))
+            .credential_provider(Arc::new(StaticCredentialProvider::new(credentials)))
+            .build()
+    }
+
+    fn work_card(afm: &str) -> CompanyWorkCard {
+        CompanyWorkCard {
+            employer_tax_identification_number: self::afm(afm),
+            business_branch_number: 1,
+            comments: None,
+            card_details: vec![],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_routes_work_cards_to_employer_sessions() {
+        let (client, transport) = client_with_responses(vec![
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("submission_response_fixture.json"),
+            ),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("submission_response_fixture.json"),
+            ),
+        ]);
+        let pool = ErganiClientPool::builder()
+            .client(client)
+            .employers(vec![
+                employer("123456783", "first"),
+                employer("987654324", "second"),
+            ])
+            .build();
+
+        let results = pool
+            .submit_work_card(vec![work_card("987654324"), work_card("123456783")])
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert!(results.values().all(Result::is_ok));
+        let requests = transport.requests();
+        assert!(requests[0].url.ends_with(AUTHENTICATION_ENDPOINT));
+        assert_eq!(requests[0].body.as_ref().unwrap()["Username"], "first");
+        assert!(requests[1].url.ends_with(WORK_CARD_ENDPOINT));
+        assert_eq!(requests[2].body.as_ref().unwrap()["Username"], "second");
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_unknown_employer_before_sending() {
+        let (client, transport) = client_with_responses(vec![]);
+        let pool = ErganiClientPool::builder()
+            .client(client)
+            .employers(vec![employer("123456783", "first")])
+            .build();
+
+        let error = pool
+            .submit_work_card(vec![work_card("123456783"), work_card("987654324")])
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            error,
+            ErganiClientError::UnknownEmployer(afm) if afm == "987654324"
+        ));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_documents_without_employer_before_sending() {
+        #[derive(serde::Serialize)]
+        struct Leave {}
+
+        impl ErganiDocument for Leave {
+            const ENDPOINT: &'static str = "/Documents/Leave";
+            const ENVELOPE_KEY: &'static str = "Leaves";
+            const ITEM_KEY: &'static str = "Leave";
+
+            type Response = SubmissionResponse;
+        }
+
+        let (client, transport) = client_with_responses(vec![]);
+        let pool = ErganiClientPool::builder()
+            .client(client)
+            .employers(vec![employer("123456783", "first")])
+            .build();
+
+        let error = pool.submit(vec![Leave {}]).await.err().unwrap();
+
+        assert!(matches!(error, ErganiClientError::MissingEmployer));
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_session_limits_concurrent_calls_per_employer() {
+        let (client, _) = client_with_responses(vec![TransportResponse::new(
+            StatusCode::OK,
+            load_fixture_as_text("authentication_response_fixture.json"),
+        )]);
+        let pool = ErganiClientPool::builder()
+            .client(client)
+            .employers(vec![employer("123456783", "first")])
+            .max_concurrent_calls(1)
+            .build();
+
+        let session = pool.session(&afm("123456783")).await.unwrap();
+        assert_eq!(
+            session.employer_tax_identification_number().await,
+            Some(afm("123456783"))
+        );
+
+        let waiting = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            pool.session(&afm("123456783")),
+        )
+        .await;
+        assert!(waiting.is_err());
+
+        drop(session);
+        assert!(pool.session(&afm("123456783")).await.is_ok());
+    }
+}
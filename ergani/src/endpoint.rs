@@ -1,4 +1,5 @@
 pub(crate) const TRIAL_API_ENDPOINT: &str = "https://trialeservices.yeka.gr/WebServicesAPI/api";
+pub(crate) const PRODUCTION_API_ENDPOINT: &str = "https://eservices.yeka.gr/WebServicesAPI/api";
 pub(crate) const AUTHENTICATION_ENDPOINT: &str = "/Authentication";
 pub(crate) const AUTHENTICATION_REFRESH_ENDPOINT: &str = "/Authentication/refresh";
 pub(crate) const WORK_CARD_ENDPOINT: &str = "/Documents/WRKCardSE";
@@ -0,0 +1,232 @@
+use std::fmt::{Display, Formatter};
+
+pub use url::{ParseError, Url};
+
+use crate::endpoint::{PRODUCTION_API_ENDPOINT, TRIAL_API_ENDPOINT};
+
+/// The Ergani API environment a client talks to
+///
+/// Clients for [ErganiEnvironment::Production] must opt in explicitly when they are built, so that
+/// a script meant for the trial environment cannot submit real documents by mistake.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ErganiEnvironment {
+    /// The trial environment, <https://trialeservices.yeka.gr/WebServicesAPI/api>
+    #[default]
+    Trial,
+    /// The production environment, <https://eservices.yeka.gr/WebServicesAPI/api>
+    Production,
+    /// Any other deployment of the Ergani API, e.g. a local mock server
+    ///
+    /// The URL must not have a query or a fragment, since endpoints are appended to it. Prefer
+    /// [ErganiEnvironment::custom], which checks it, clients refuse to be built otherwise.
+    Custom(Url),
+}
+
+/// Why a URL cannot be the base URL of the Ergani API
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum BaseUrlError {
+    /// The base URL is not a valid URL
+    Invalid(#[source] ParseError),
+    /// The base URL has a query or a fragment, which the endpoints would be appended to
+    QueryOrFragment(Url),
+}
+
+impl Display for BaseUrlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BaseUrlError::Invalid(error) => write!(f, "Invalid base URL: {error}"),
+            BaseUrlError::QueryOrFragment(url) => {
+                write!(f, "The base URL {url} must not have a query or a fragment")
+            }
+        }
+    }
+}
+
+impl From<ParseError> for BaseUrlError {
+    fn from(error: ParseError) -> Self {
+        BaseUrlError::Invalid(error)
+    }
+}
+
+impl ErganiEnvironment {
+    /// Returns a custom environment with the given base URL
+    ///
+    /// # Arguments
+    /// * - `url` - The base URL of the Ergani API
+    ///
+    /// # Errors
+    /// * - `[BaseUrlError::QueryOrFragment]` - Raised if the base URL has a query or a fragment
+    pub fn custom(url: Url) -> Result<Self, BaseUrlError> {
+        check_base_url(&url)?;
+
+        Ok(ErganiEnvironment::Custom(url))
+    }
+
+    /// Returns the environment with the given base URL, recognizing the trial and production URLs
+    ///
+    /// # Arguments
+    /// * - `base_url` - The base URL of the Ergani API
+    ///
+    /// # Errors
+    /// * - `[BaseUrlError::Invalid]` - Raised if the base URL is not a valid URL
+    /// * - `[BaseUrlError::QueryOrFragment]` - Raised if the base URL has a query or a fragment
+    pub fn from_base_url(base_url: &str) -> Result<Self, BaseUrlError> {
+        let url = Url::parse(base_url)?;
+
+        for environment in [ErganiEnvironment::Trial, ErganiEnvironment::Production] {
+            if Url::parse(environment.base_url()).is_ok_and(|known| same_base_url(&url, &known)) {
+                return Ok(environment);
+            }
+        }
+
+        ErganiEnvironment::custom(url)
+    }
+
+    /// Checks that the base URL of this environment can have endpoints appended to it
+    ///
+    /// # Errors
+    /// * - `[BaseUrlError::QueryOrFragment]` - Raised if a custom base URL has a query or a fragment
+    pub(crate) fn check(&self) -> Result<(), BaseUrlError> {
+        match self {
+            ErganiEnvironment::Custom(url) => check_base_url(url),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the base URL of the Ergani API in this environment, without a trailing slash
+    pub fn base_url(&self) -> &str {
+        match self {
+            ErganiEnvironment::Trial => TRIAL_API_ENDPOINT,
+            ErganiEnvironment::Production => PRODUCTION_API_ENDPOINT,
+            ErganiEnvironment::Custom(url) => url.as_str().trim_end_matches('/'),
+        }
+    }
+
+    /// Returns whether real documents are submitted in this environment, i.e. for
+    /// [ErganiEnvironment::Production] and for any custom URL on the production host
+    pub fn is_production(&self) -> bool {
+        match self {
+            ErganiEnvironment::Trial => false,
+            ErganiEnvironment::Production => true,
+            ErganiEnvironment::Custom(url) => {
+                let production_host = Url::parse(PRODUCTION_API_ENDPOINT)
+                    .ok()
+                    .and_then(|production| production.host_str().map(str::to_owned));
+
+                url.host_str().map(|host| host.trim_end_matches('.')) == production_host.as_deref()
+            }
+        }
+    }
+}
+
+/// Checks that endpoints can be appended to a base URL, which they cannot after a query or a fragment
+fn check_base_url(url: &Url) -> Result<(), BaseUrlError> {
+    if url.query().is_some() || url.fragment().is_some() {
+        return Err(BaseUrlError::QueryOrFragment(url.clone()));
+    }
+
+    Ok(())
+}
+
+/// Checks whether two parsed URLs point to the same base URL, ignoring a trailing slash
+///
+/// Parsing already lowercases the scheme and the host, so only the path, the port, the query and
+/// the fragment are left to compare.
+fn same_base_url(url: &Url, known: &Url) -> bool {
+    url.scheme() == known.scheme()
+        && url.host_str() == known.host_str()
+        && url.port_or_known_default() == known.port_or_known_default()
+        && url.path().trim_end_matches('/') == known.path().trim_end_matches('/')
+        && url.query().is_none()
+        && url.fragment().is_none()
+}
+
+impl Display for ErganiEnvironment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ErganiEnvironment::Trial => write!(f, "trial"),
+            ErganiEnvironment::Production => write!(f, "production"),
+            ErganiEnvironment::Custom(url) => write!(f, "custom ({url})"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_base_url_recognizes_known_environments() {
+        assert_eq!(
+            ErganiEnvironment::from_base_url(TRIAL_API_ENDPOINT),
+            Ok(ErganiEnvironment::Trial)
+        );
+        assert_eq!(
+            ErganiEnvironment::from_base_url("https://eservices.yeka.gr/WebServicesAPI/api/"),
+            Ok(ErganiEnvironment::Production)
+        );
+    }
+
+    #[test]
+    fn test_custom_base_url_has_no_trailing_slash() {
+        let environment = ErganiEnvironment::from_base_url("http://localhost:8080").unwrap();
+
+        assert_eq!(environment.base_url(), "http://localhost:8080");
+        assert_eq!(environment.to_string(), "custom (http://localhost:8080/)");
+        assert!(!environment.is_production());
+    }
+
+    #[test]
+    fn test_production_host_is_recognized_in_any_form() {
+        let production = Url::parse(PRODUCTION_API_ENDPOINT).unwrap();
+
+        assert!(ErganiEnvironment::Custom(production).is_production());
+        assert_eq!(
+            ErganiEnvironment::from_base_url("HTTPS://ESERVICES.yeka.gr/WebServicesAPI/api"),
+            Ok(ErganiEnvironment::Production)
+        );
+
+        assert!(ErganiEnvironment::Custom(
+            Url::parse("https://eservices.yeka.gr/WebServicesAPI/api?x").unwrap()
+        )
+        .is_production());
+
+        for base_url in [
+            "http://eservices.yeka.gr/WebServicesAPI/api",
+            "https://eservices.yeka.gr./WebServicesAPI/api",
+            "https://eservices.yeka.gr:443/other",
+        ] {
+            let environment = ErganiEnvironment::from_base_url(base_url).unwrap();
+
+            assert!(environment.is_production(), "{base_url} is not production");
+        }
+
+        assert!(!ErganiEnvironment::Custom(
+            Url::parse("https://trialeservices.yeka.gr/WebServicesAPI/api?x").unwrap()
+        )
+        .is_production());
+    }
+
+    #[test]
+    fn test_from_base_url_rejects_invalid_url() {
+        assert!(matches!(
+            ErganiEnvironment::from_base_url("not a url"),
+            Err(BaseUrlError::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn test_base_url_with_query_or_fragment_is_rejected() {
+        for base_url in ["https://host/api?x=1", "https://host/api#section"] {
+            assert!(matches!(
+                ErganiEnvironment::from_base_url(base_url),
+                Err(BaseUrlError::QueryOrFragment(_))
+            ));
+        }
+
+        let url = Url::parse("https://host/api?x=1").unwrap();
+        assert!(ErganiEnvironment::custom(url.clone()).is_err());
+        assert!(ErganiEnvironment::Custom(url).check().is_err());
+        assert!(ErganiEnvironment::custom(Url::parse("https://host/api").unwrap()).is_ok());
+    }
+}
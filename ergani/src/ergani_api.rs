@@ -0,0 +1,441 @@
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use bon::Builder;
+
+use crate::api_error::ErganiClientError;
+use crate::auth::authenticator::ErganiAuthenticationState;
+use crate::client::{ErganiClient, SubmissionResponse};
+use crate::ergani_fetch_response::ErganiFetchResponse;
+use crate::models::company::company_daily_schedule::CompanyDailySchedule;
+use crate::models::company::company_overtime::CompanyOvertime;
+use crate::models::company::company_weekly_schedule::CompanyWeeklySchedule;
+use crate::models::company::company_work_card::CompanyWorkCard;
+use crate::responses::day_schedule_response::DayScheduleResponseRoot;
+use crate::responses::lookup_response::LookupRoot;
+use crate::responses::overtime_response::OvertimeResponseRoot;
+use crate::responses::week_schedule_response::WeekScheduleResponseRoot;
+use crate::responses::work_card_response::WorkCardResponseRoot;
+use crate::submission_outcome::SubmissionOutcome;
+
+/// The operations of the Ergani API
+///
+/// [ErganiClient] implements it by calling the Ergani API. Depend on this trait instead of the client
+/// to swap in a fake, such as [RecordingErganiApi], in tests.
+#[async_trait]
+pub trait ErganiApi: Send + Sync {
+    /// Submits work card records (check-in, check-out) for employees
+    ///
+    /// See [ErganiClient::submit_work_card]
+    async fn submit_work_card(
+        &self,
+        company_work_cards: Vec<CompanyWorkCard>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError>;
+
+    /// Submits overtime records for employees
+    ///
+    /// See [ErganiClient::submit_overtime]
+    async fn submit_overtime(
+        &self,
+        company_overtimes: Vec<CompanyOvertime>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError>;
+
+    /// Submits schedule records that are updated on a daily basis for employees
+    ///
+    /// See [ErganiClient::submit_daily_schedule]
+    async fn submit_daily_schedule(
+        &self,
+        company_daily_schedules: Vec<CompanyDailySchedule>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError>;
+
+    /// Submits weekly schedule records for employees
+    ///
+    /// See [ErganiClient::submit_weekly_schedule]
+    async fn submit_weekly_schedule(
+        &self,
+        company_weekly_schedules: Vec<CompanyWeeklySchedule>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError>;
+
+    /// Fetches the submissions
+    ///
+    /// See [ErganiClient::fetch_submissions]
+    async fn fetch_submissions(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<LookupRoot>, ErganiClientError>;
+
+    /// Fetches the weekly schedule
+    ///
+    /// See [ErganiClient::fetch_weekly_schedule]
+    async fn fetch_weekly_schedule(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<WeekScheduleResponseRoot>, ErganiClientError>;
+
+    /// Fetches the daily schedule
+    ///
+    /// See [ErganiClient::fetch_daily_schedule]
+    async fn fetch_daily_schedule(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<DayScheduleResponseRoot>, ErganiClientError>;
+
+    /// Fetches the work cards
+    ///
+    /// See [ErganiClient::fetch_work_cards]
+    async fn fetch_work_cards(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<WorkCardResponseRoot>, ErganiClientError>;
+
+    /// Fetches the overtime records
+    ///
+    /// See [ErganiClient::fetch_overtimes]
+    async fn fetch_overtimes(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<OvertimeResponseRoot>, ErganiClientError>;
+}
+
+#[async_trait]
+impl ErganiApi for ErganiClient {
+    async fn submit_work_card(
+        &self,
+        company_work_cards: Vec<CompanyWorkCard>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        ErganiClient::submit_work_card(self, company_work_cards, auth_state).await
+    }
+
+    async fn submit_overtime(
+        &self,
+        company_overtimes: Vec<CompanyOvertime>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        ErganiClient::submit_overtime(self, company_overtimes, auth_state).await
+    }
+
+    async fn submit_daily_schedule(
+        &self,
+        company_daily_schedules: Vec<CompanyDailySchedule>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        ErganiClient::submit_daily_schedule(self, company_daily_schedules, auth_state).await
+    }
+
+    async fn submit_weekly_schedule(
+        &self,
+        company_weekly_schedules: Vec<CompanyWeeklySchedule>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        ErganiClient::submit_weekly_schedule(self, company_weekly_schedules, auth_state).await
+    }
+
+    async fn fetch_submissions(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<LookupRoot>, ErganiClientError> {
+        ErganiClient::fetch_submissions(self, auth_state).await
+    }
+
+    async fn fetch_weekly_schedule(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<WeekScheduleResponseRoot>, ErganiClientError> {
+        ErganiClient::fetch_weekly_schedule(self, auth_state).await
+    }
+
+    async fn fetch_daily_schedule(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<DayScheduleResponseRoot>, ErganiClientError> {
+        ErganiClient::fetch_daily_schedule(self, auth_state).await
+    }
+
+    async fn fetch_work_cards(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<WorkCardResponseRoot>, ErganiClientError> {
+        ErganiClient::fetch_work_cards(self, auth_state).await
+    }
+
+    async fn fetch_overtimes(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<OvertimeResponseRoot>, ErganiClientError> {
+        ErganiClient::fetch_overtimes(self, auth_state).await
+    }
+}
+
+/// An [ErganiApi] that records the submitted documents instead of sending them and returns configured responses
+/// * - `submission_responses` - The receipts returned by every submission. Defaults to none.
+/// * - `submissions` - The response of [ErganiApi::fetch_submissions]. Defaults to none.
+/// * - `weekly_schedule` - The response of [ErganiApi::fetch_weekly_schedule]. Defaults to none.
+/// * - `daily_schedule` - The response of [ErganiApi::fetch_daily_schedule]. Defaults to none.
+/// * - `work_cards` - The response of [ErganiApi::fetch_work_cards]. Defaults to none.
+/// * - `overtimes` - The response of [ErganiApi::fetch_overtimes]. Defaults to none.
+///
+/// The authentication state passed to each call is returned unchanged. Queue errors with
+/// [RecordingErganiApi::fail_next] to exercise failure paths.
+#[derive(Builder, Default)]
+pub struct RecordingErganiApi {
+    #[builder(default)]
+    submission_responses: Vec<SubmissionResponse>,
+    submissions: Option<LookupRoot>,
+    weekly_schedule: Option<WeekScheduleResponseRoot>,
+    daily_schedule: Option<DayScheduleResponseRoot>,
+    work_cards: Option<WorkCardResponseRoot>,
+    overtimes: Option<OvertimeResponseRoot>,
+    #[builder(skip)]
+    submitted_work_cards: Mutex<Vec<CompanyWorkCard>>,
+    #[builder(skip)]
+    submitted_overtimes: Mutex<Vec<CompanyOvertime>>,
+    #[builder(skip)]
+    submitted_daily_schedules: Mutex<Vec<CompanyDailySchedule>>,
+    #[builder(skip)]
+    submitted_weekly_schedules: Mutex<Vec<CompanyWeeklySchedule>>,
+    #[builder(skip)]
+    queued_errors: Mutex<VecDeque<ErganiClientError>>,
+}
+
+impl RecordingErganiApi {
+    /// Makes the next call, whether a submission or a fetch, fail with the given error.
+    /// Errors queued by multiple calls are returned in order.
+    ///
+    /// # Arguments:
+    /// * - `error` - The error to return
+    pub fn fail_next(&self, error: ErganiClientError) {
+        self.queued_errors.lock().unwrap().push_back(error);
+    }
+
+    /// Returns the work cards submitted so far, in order
+    pub fn submitted_work_cards(&self) -> Vec<CompanyWorkCard> {
+        self.submitted_work_cards.lock().unwrap().clone()
+    }
+
+    /// Returns the overtimes submitted so far, in order
+    pub fn submitted_overtimes(&self) -> Vec<CompanyOvertime> {
+        self.submitted_overtimes.lock().unwrap().clone()
+    }
+
+    /// Returns the daily schedules submitted so far, in order
+    pub fn submitted_daily_schedules(&self) -> Vec<CompanyDailySchedule> {
+        self.submitted_daily_schedules.lock().unwrap().clone()
+    }
+
+    /// Returns the weekly schedules submitted so far, in order
+    pub fn submitted_weekly_schedules(&self) -> Vec<CompanyWeeklySchedule> {
+        self.submitted_weekly_schedules.lock().unwrap().clone()
+    }
+
+    /// Records the submitted documents and returns the configured receipts, unless an error is queued
+    ///
+    /// # Arguments:
+    /// * - `recorded` - The documents recorded so far for this kind of submission
+    /// * - `documents` - The submitted documents
+    /// * - `auth_state` - The authentication state of the call
+    fn _record<T>(
+        &self,
+        recorded: &Mutex<Vec<T>>,
+        documents: Vec<T>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self._take_queued_error()?;
+
+        recorded.lock().unwrap().extend(documents);
+
+        Ok(SubmissionOutcome::builder()
+            .submissions(self.submission_responses.clone())
+            .auth_state(auth_state)
+            .build())
+    }
+
+    /// Returns the configured fetch response, unless an error is queued
+    ///
+    /// # Arguments:
+    /// * - `response` - The configured response
+    /// * - `auth_state` - The authentication state of the call
+    fn _fetch<T: Clone>(
+        &self,
+        response: &Option<T>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<T>, ErganiClientError> {
+        self._take_queued_error()?;
+
+        Ok(ErganiFetchResponse::builder()
+            .maybe_response(response.clone())
+            .auth_state(auth_state)
+            .build())
+    }
+
+    fn _take_queued_error(&self) -> Result<(), ErganiClientError> {
+        match self.queued_errors.lock().unwrap().pop_front() {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+#[async_trait]
+impl ErganiApi for RecordingErganiApi {
+    async fn submit_work_card(
+        &self,
+        company_work_cards: Vec<CompanyWorkCard>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self._record(&self.submitted_work_cards, company_work_cards, auth_state)
+    }
+
+    async fn submit_overtime(
+        &self,
+        company_overtimes: Vec<CompanyOvertime>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self._record(&self.submitted_overtimes, company_overtimes, auth_state)
+    }
+
+    async fn submit_daily_schedule(
+        &self,
+        company_daily_schedules: Vec<CompanyDailySchedule>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self._record(
+            &self.submitted_daily_schedules,
+            company_daily_schedules,
+            auth_state,
+        )
+    }
+
+    async fn submit_weekly_schedule(
+        &self,
+        company_weekly_schedules: Vec<CompanyWeeklySchedule>,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        self._record(
+            &self.submitted_weekly_schedules,
+            company_weekly_schedules,
+            auth_state,
+        )
+    }
+
+    async fn fetch_submissions(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<LookupRoot>, ErganiClientError> {
+        self._fetch(&self.submissions, auth_state)
+    }
+
+    async fn fetch_weekly_schedule(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<WeekScheduleResponseRoot>, ErganiClientError> {
+        self._fetch(&self.weekly_schedule, auth_state)
+    }
+
+    async fn fetch_daily_schedule(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<DayScheduleResponseRoot>, ErganiClientError> {
+        self._fetch(&self.daily_schedule, auth_state)
+    }
+
+    async fn fetch_work_cards(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<WorkCardResponseRoot>, ErganiClientError> {
+        self._fetch(&self.work_cards, auth_state)
+    }
+
+    async fn fetch_overtimes(
+        &self,
+        auth_state: ErganiAuthenticationState,
+    ) -> Result<ErganiFetchResponse<OvertimeResponseRoot>, ErganiClientError> {
+        self._fetch(&self.overtimes, auth_state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::internal::tests::auth_state;
+    use crate::responses::lookup_response::LookupResponse;
+    use chrono::Utc;
+
+    fn company_work_card(business_branch_number: i64) -> CompanyWorkCard {
+        CompanyWorkCard {
+            employer_tax_identification_number: "123456783".parse().unwrap(),
+            business_branch_number,
+            comments: None,
+            card_details: vec![],
+        }
+    }
+
+    async fn submit_branches(
+        api: &dyn ErganiApi,
+        branches: Vec<i64>,
+    ) -> Result<SubmissionOutcome, ErganiClientError> {
+        let company_work_cards = branches.into_iter().map(company_work_card).collect();
+
+        api.submit_work_card(company_work_cards, auth_state().build())
+            .await
+    }
+
+    #[tokio::test]
+    async fn test_recording_api_records_submissions_and_returns_receipts() {
+        let submission_response = SubmissionResponse::builder()
+            .id("142".to_string())
+            .protocol("ΕΡΓ123456".to_string())
+            .submit_date(Utc::now())
+            .build();
+        let api = RecordingErganiApi::builder()
+            .submission_responses(vec![submission_response])
+            .build();
+
+        let outcome = submit_branches(&api, vec![0, 1]).await.unwrap();
+
+        assert_eq!(outcome.submissions()[0].protocol(), "ΕΡΓ123456");
+        let branches: Vec<i64> = api
+            .submitted_work_cards()
+            .iter()
+            .map(|company_work_card| company_work_card.business_branch_number)
+            .collect();
+        assert_eq!(branches, vec![0, 1]);
+        assert!(api.submitted_overtimes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_recording_api_returns_configured_fetch_response() {
+        let lookup_response = LookupResponse {
+            id: 1,
+            code: "WRKCardSE".to_string(),
+            description: "Κάρτα Εργασίας".to_string(),
+        };
+        let api = RecordingErganiApi::builder()
+            .submissions(vec![lookup_response.clone()])
+            .build();
+
+        let submissions = api.fetch_submissions(auth_state().build()).await.unwrap();
+        let work_cards = api.fetch_work_cards(auth_state().build()).await.unwrap();
+
+        assert_eq!(submissions.response(), Some(&vec![lookup_response]));
+        assert!(work_cards.response().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recording_api_fails_with_queued_error_once() {
+        let api = RecordingErganiApi::default();
+        api.fail_next(ErganiClientError::Validation("invalid".to_string()));
+
+        let failed = submit_branches(&api, vec![0]).await;
+        let succeeded = submit_branches(&api, vec![1]).await;
+
+        assert!(matches!(failed, Err(ErganiClientError::Validation(_))));
+        assert!(succeeded.is_ok());
+        assert_eq!(api.submitted_work_cards().len(), 1);
+    }
+}
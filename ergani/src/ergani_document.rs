@@ -0,0 +1,93 @@
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::api_error::ErganiClientError;
+use crate::models::types::afm::Afm;
+use crate::validation::ValidationIssue;
+
+/// A document that can be submitted to the Ergani API
+///
+/// Documents are sent as a list wrapped in an envelope, e.g. work cards are sent as
+/// `{"Cards": {"Card": [...]}}`. Implement this trait for a new document type to submit it with
+/// [ErganiClient::submit](crate::client::ErganiClient::submit).
+pub trait ErganiDocument: Serialize + Send + Sync {
+    /// The endpoint the document is submitted to, relative to the base URL of the Ergani API
+    const ENDPOINT: &'static str;
+    /// The key of the outer envelope object, e.g. `Cards`
+    const ENVELOPE_KEY: &'static str;
+    /// The key of the list of documents inside the envelope, e.g. `Card`
+    const ITEM_KEY: &'static str;
+
+    /// The receipt returned by the Ergani API for each submitted document
+    type Response: DeserializeOwned + Send;
+
+    /// Returns the AFM of the employer the document is submitted for, if the document carries one
+    ///
+    /// Sessions acting for a specific employer refuse documents for other employers.
+    fn employer_tax_identification_number(&self) -> Option<&Afm> {
+        None
+    }
+
+    /// Returns every issue that would make the Ergani API reject the document
+    ///
+    /// Submissions are refused before anything is sent if a document has issues. Documents implementing
+    /// [Validate](crate::validation::Validate) return its issues.
+    fn validation_issues(&self) -> Vec<ValidationIssue> {
+        vec![]
+    }
+}
+
+/// Serializes the documents into the envelope expected by the Ergani API
+///
+/// # Arguments:
+/// * - `documents` - The documents to be submitted
+///
+/// # Errors:
+/// * - `[ErganiClientError::Serialization]` - Raised if the documents could not be serialized
+pub(crate) fn to_envelope<D: ErganiDocument>(documents: &[D]) -> Result<Value, ErganiClientError> {
+    let params = serde_json::to_value(documents).map_err(ErganiClientError::Serialization)?;
+
+    Ok(json!({
+        D::ENVELOPE_KEY: {
+            D::ITEM_KEY: params
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Serialize)]
+    struct Leave {
+        #[serde(rename = "f_afm")]
+        employee_tax_identification_number: String,
+    }
+
+    #[derive(Deserialize)]
+    struct LeaveReceipt {}
+
+    impl ErganiDocument for Leave {
+        const ENDPOINT: &'static str = "/Documents/Leave";
+        const ENVELOPE_KEY: &'static str = "Leaves";
+        const ITEM_KEY: &'static str = "Leave";
+
+        type Response = LeaveReceipt;
+    }
+
+    #[test]
+    fn test_to_envelope_wraps_documents_in_declared_keys() {
+        let documents = vec![Leave {
+            employee_tax_identification_number: "123456789".to_string(),
+        }];
+
+        let envelope = to_envelope(&documents).unwrap();
+
+        assert_eq!(
+            envelope,
+            json!({"Leaves": {"Leave": [{"f_afm": "123456789"}]}})
+        );
+    }
+}
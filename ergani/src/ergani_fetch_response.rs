@@ -16,4 +16,8 @@ impl<T> ErganiFetchResponse<T> {
     pub fn auth_state(&self) -> &ErganiAuthenticationState {
         &self.auth_state
     }
+
+    pub fn into_response(self) -> Option<T> {
+        self.response
+    }
 }
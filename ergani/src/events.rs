@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use tokio::sync::broadcast;
+
+use crate::auth::authenticator::ErganiAuthenticationState;
+use crate::environment::ErganiEnvironment;
+
+/// How many events a subscriber may lag behind before it misses the oldest ones
+pub(crate) const DEFAULT_EVENT_CAPACITY: usize = 64;
+
+/// An event in the authentication lifecycle of a client, or a submission it made
+///
+/// Subscribe to the events of a client with [ErganiClient::subscribe](crate::client::ErganiClient::subscribe).
+/// Events carry no secrets in readable form: the tokens of an authentication state are
+/// [SecretString](crate::secret::SecretString)s, so events can be logged as they are.
+#[derive(Clone, Debug)]
+pub struct ErganiEvent {
+    occurred_at: DateTime<Utc>,
+    kind: ErganiEventKind,
+}
+
+impl ErganiEvent {
+    pub fn occurred_at(&self) -> &DateTime<Utc> {
+        &self.occurred_at
+    }
+
+    pub fn kind(&self) -> &ErganiEventKind {
+        &self.kind
+    }
+}
+
+/// What happened in an [ErganiEvent]
+#[non_exhaustive]
+#[derive(Clone, Debug)]
+pub enum ErganiEventKind {
+    /// A login succeeded
+    /// * - `username` - The username that logged in
+    /// * - `auth_state` - The new authentication state, e.g. to persist it
+    LoggedIn {
+        username: String,
+        auth_state: ErganiAuthenticationState,
+    },
+    /// The access token was refreshed
+    /// * - `auth_state` - The refreshed authentication state, e.g. to persist it
+    TokenRefreshed {
+        auth_state: ErganiAuthenticationState,
+    },
+    /// A refresh of the access token failed
+    /// * - `error` - The description of the error
+    RefreshFailed { error: String },
+    /// The client logged in again with its credential provider, after the refresh token expired
+    /// * - `auth_state` - The new authentication state, e.g. to persist it
+    ReloginPerformed {
+        auth_state: ErganiAuthenticationState,
+    },
+    /// The Ergani API accepted a submission
+    /// * - `endpoint` - The endpoint the documents were submitted to
+    /// * - `submissions` - How many receipts the Ergani API returned
+    /// * - `environment` - The environment the documents were submitted to
+    SubmissionAccepted {
+        endpoint: String,
+        submissions: usize,
+        environment: ErganiEnvironment,
+    },
+}
+
+/// Broadcasts [ErganiEvent]s to every subscriber of a client and its authenticators
+#[derive(Clone, Debug)]
+pub(crate) struct EventEmitter {
+    sender: broadcast::Sender<ErganiEvent>,
+}
+
+impl EventEmitter {
+    /// Creates an emitter that keeps up to `capacity` events for lagging subscribers
+    pub(crate) fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+
+        EventEmitter { sender }
+    }
+
+    pub(crate) fn subscribe(&self) -> broadcast::Receiver<ErganiEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Emits an event that happened now, if anyone is subscribed
+    pub(crate) fn emit(&self, kind: ErganiEventKind) {
+        // Sending only fails when there are no subscribers, which is fine
+        let _ = self.sender.send(ErganiEvent {
+            occurred_at: Utc::now(),
+            kind,
+        });
+    }
+}
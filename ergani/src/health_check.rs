@@ -0,0 +1,125 @@
+use std::fmt::{Display, Formatter};
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::auth::authenticator::ErganiAuthenticationState;
+use crate::auth::login_payload::LoginPayload;
+use crate::environment::ErganiEnvironment;
+
+/// How an [ErganiClient::health_check](crate::client::ErganiClient::health_check) authenticates
+pub enum HealthCheckAuth {
+    /// Logs in with the given credentials
+    Login(LoginPayload),
+    /// Refreshes the given authentication state
+    Refresh(ErganiAuthenticationState),
+}
+
+impl From<LoginPayload> for HealthCheckAuth {
+    fn from(login_payload: LoginPayload) -> Self {
+        HealthCheckAuth::Login(login_payload)
+    }
+}
+
+impl From<ErganiAuthenticationState> for HealthCheckAuth {
+    fn from(auth_state: ErganiAuthenticationState) -> Self {
+        HealthCheckAuth::Refresh(auth_state)
+    }
+}
+
+/// A step of a health check
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HealthCheckStep {
+    /// Logging in with credentials
+    Login,
+    /// Refreshing an authentication state
+    Refresh,
+    /// Reading the submission types, a lightweight authenticated request
+    Lookup,
+}
+
+impl Display for HealthCheckStep {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HealthCheckStep::Login => write!(f, "login"),
+            HealthCheckStep::Refresh => write!(f, "refresh"),
+            HealthCheckStep::Lookup => write!(f, "lookup"),
+        }
+    }
+}
+
+/// The outcome of one step of a health check
+/// * - `step` - The step
+/// * - `latency` - How long the step took
+/// * - `error` - The description of the error, if the step failed
+#[derive(Clone, Debug)]
+pub struct HealthCheckStepReport {
+    pub(crate) step: HealthCheckStep,
+    pub(crate) latency: Duration,
+    pub(crate) error: Option<String>,
+}
+
+impl HealthCheckStepReport {
+    pub fn step(&self) -> HealthCheckStep {
+        self.step
+    }
+
+    pub fn latency(&self) -> Duration {
+        self.latency
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// The outcome of a health check
+/// * - `environment` - The environment that was checked
+/// * - `steps` - The steps that ran, in order. The health check stops at the first failed step.
+/// * - `token_expires_at` - When the access token obtained by the health check expires
+/// * - `clock_skew` - How far the clock of the Ergani API is ahead of the local clock
+/// * - `auth_state` - The authentication state obtained by the health check, e.g. to keep using it
+#[derive(Clone, Debug)]
+pub struct HealthCheckReport {
+    pub(crate) environment: ErganiEnvironment,
+    pub(crate) steps: Vec<HealthCheckStepReport>,
+    pub(crate) token_expires_at: Option<DateTime<Utc>>,
+    pub(crate) clock_skew: Option<TimeDelta>,
+    pub(crate) auth_state: Option<ErganiAuthenticationState>,
+}
+
+impl HealthCheckReport {
+    pub fn environment(&self) -> &ErganiEnvironment {
+        &self.environment
+    }
+
+    pub fn steps(&self) -> &[HealthCheckStepReport] {
+        &self.steps
+    }
+
+    pub fn token_expires_at(&self) -> Option<&DateTime<Utc>> {
+        self.token_expires_at.as_ref()
+    }
+
+    pub fn clock_skew(&self) -> Option<TimeDelta> {
+        self.clock_skew
+    }
+
+    pub fn auth_state(&self) -> Option<&ErganiAuthenticationState> {
+        self.auth_state.as_ref()
+    }
+
+    /// Returns the step that failed, if any
+    pub fn failed_step(&self) -> Option<&HealthCheckStepReport> {
+        self.steps.iter().find(|step| !step.is_success())
+    }
+
+    /// Returns whether every step succeeded
+    pub fn is_healthy(&self) -> bool {
+        self.failed_step().is_none()
+    }
+}
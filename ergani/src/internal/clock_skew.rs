@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use reqwest::header::{HeaderMap, DATE};
+
+/// Marks that no skew has been measured yet
+const UNMEASURED: i64 = i64::MIN;
+
+/// The offset of the clock of the Ergani API from the local clock, measured from the `Date` header of its responses
+#[derive(Debug)]
+pub(crate) struct ClockSkew {
+    skew_ms: AtomicI64,
+}
+
+impl Default for ClockSkew {
+    fn default() -> Self {
+        ClockSkew {
+            skew_ms: AtomicI64::new(UNMEASURED),
+        }
+    }
+}
+
+impl ClockSkew {
+    /// Measures the skew from the `Date` header of a response, if it has a valid one
+    ///
+    /// # Arguments
+    /// * - `headers` - The headers of the response
+    pub(crate) fn record(&self, headers: &HeaderMap) {
+        let Some(server_time) = parse_date(headers) else {
+            return;
+        };
+
+        let skew = server_time - Utc::now();
+        self.skew_ms
+            .store(skew.num_milliseconds(), Ordering::Relaxed);
+    }
+
+    /// Returns how far the server clock is ahead of the local clock, if it has been measured
+    pub(crate) fn skew(&self) -> Option<TimeDelta> {
+        match self.skew_ms.load(Ordering::Relaxed) {
+            UNMEASURED => None,
+            skew_ms => Some(TimeDelta::milliseconds(skew_ms)),
+        }
+    }
+
+    /// Returns the current time according to the server clock, or the local clock if no skew has been measured
+    pub(crate) fn server_now(&self) -> DateTime<Utc> {
+        Utc::now() + self.skew().unwrap_or_default()
+    }
+}
+
+fn parse_date(headers: &HeaderMap) -> Option<DateTime<Utc>> {
+    let value = headers.get(DATE)?.to_str().ok()?;
+
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_clock_skew_is_measured_from_date_header() {
+        let clock_skew = ClockSkew::default();
+        assert!(clock_skew.skew().is_none());
+
+        let server_time = Utc::now() + TimeDelta::minutes(10);
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            DATE,
+            HeaderValue::from_str(&server_time.to_rfc2822()).unwrap(),
+        );
+        clock_skew.record(&headers);
+
+        let skew = clock_skew.skew().unwrap();
+        assert!((skew - TimeDelta::minutes(10)).num_seconds().abs() <= 1);
+    }
+
+    #[test]
+    fn test_clock_skew_ignores_invalid_date_header() {
+        let clock_skew = ClockSkew::default();
+        let mut headers = HeaderMap::new();
+        headers.insert(DATE, HeaderValue::from_static("yesterday"));
+
+        clock_skew.record(&headers);
+
+        assert!(clock_skew.skew().is_none());
+    }
+}
@@ -1,6 +1,19 @@
+use crate::api_error::ErganiClientError;
 use chrono::{DateTime, Utc};
 use serde::de;
-use serde::de::Visitor;
+use serde::de::{DeserializeOwned, Visitor};
+
+/// Deserializes the body of an Ergani API response
+///
+/// # Arguments
+/// * - `body` - The raw body of the response
+///
+/// # Errors
+/// * - `[ErganiClientError::Deserialization]` - Raised with the raw body if it could not be deserialized
+pub(crate) fn deserialize_body<T: DeserializeOwned>(body: String) -> Result<T, ErganiClientError> {
+    serde_json::from_str(&body)
+        .map_err(|source| ErganiClientError::Deserialization { source, body })
+}
 
 pub(crate) fn deserialize_datetime<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
 where
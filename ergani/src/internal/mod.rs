@@ -1,3 +1,4 @@
+pub(crate) mod clock_skew;
 pub(crate) mod deserializers;
 #[cfg(test)]
 pub(crate) mod tests;
@@ -1,5 +1,17 @@
+use async_trait::async_trait;
+use bon::builder;
+use chrono::{DateTime, TimeDelta, Utc};
 use regex::Regex;
+use std::collections::VecDeque;
 use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use crate::auth::authenticator::ErganiAuthenticationState;
+use crate::auth::user_type::UserType;
+use crate::client::ErganiClient;
+use crate::models::types::afm::Afm;
+use crate::retry_policy::RetryPolicy;
+use crate::transport::{ErganiTransport, TransportError, TransportRequest, TransportResponse};
 
 #[cfg(test)]
 pub fn load_fixture_as_text(fixture_name: &str) -> String {
@@ -13,3 +25,104 @@ pub fn load_fixture_as_text(fixture_name: &str) -> String {
         .replace("\": ", "\":"); // Hacky way to convert multi-line JSON to single-line
     regex.replace_all(&fixture_text, "").to_string()
 }
+
+/// Answers a request of a [MockTransport] depending on the request
+type Responder = Box<dyn Fn(&TransportRequest) -> TransportResponse + Send + Sync>;
+
+/// A transport that returns queued responses or errors in order, or the answer of a responder, and records every
+/// request it receives
+///
+/// Every send yields to the runtime first, so that concurrent tasks interleave as they would over a network.
+#[derive(Default)]
+pub struct MockTransport {
+    responses: Mutex<VecDeque<Result<TransportResponse, TransportError>>>,
+    responder: Option<Responder>,
+    requests: Mutex<Vec<TransportRequest>>,
+}
+
+impl MockTransport {
+    pub fn with_responses(responses: Vec<TransportResponse>) -> Self {
+        MockTransport::with_results(responses.into_iter().map(Ok).collect())
+    }
+
+    pub fn with_results(results: Vec<Result<TransportResponse, TransportError>>) -> Self {
+        MockTransport {
+            responses: Mutex::new(results.into()),
+            ..MockTransport::default()
+        }
+    }
+
+    pub fn with_responder(
+        responder: impl Fn(&TransportRequest) -> TransportResponse + Send + Sync + 'static,
+    ) -> Self {
+        MockTransport {
+            responder: Some(Box::new(responder)),
+            ..MockTransport::default()
+        }
+    }
+
+    pub fn requests(&self) -> Vec<TransportRequest> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl ErganiTransport for MockTransport {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, TransportError> {
+        tokio::task::yield_now().await;
+        self.requests.lock().unwrap().push(request.clone());
+
+        if let Some(responder) = &self.responder {
+            return Ok(responder(&request));
+        }
+
+        self.responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .expect("no response queued for the request")
+    }
+}
+
+/// Returns a client without retries that sends its requests to a [MockTransport] returning `responses`
+pub fn client_with_responses(
+    responses: Vec<TransportResponse>,
+) -> (ErganiClient, Arc<MockTransport>) {
+    let transport = Arc::new(MockTransport::with_responses(responses));
+    let client = ErganiClient::builder()
+        .transport(transport.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap();
+
+    (client, transport)
+}
+
+/// Returns an authentication state whose access token expires in 2100 and whose refresh token expires in a day,
+/// unless overridden
+///
+/// # Arguments
+/// * - `access_token` - The access token. Defaults to `access-token`.
+/// * - `access_token_expires_at` - When the access token expires
+/// * - `refresh_token_expires_at` - When the refresh token expires
+/// * - `user_type` - The category of the user
+/// * - `employer_tax_identification_number` - The AFM of the employer given at login
+#[builder(finish_fn = build)]
+pub fn auth_state(
+    #[builder(into, default = "access-token".to_string())] access_token: String,
+    access_token_expires_at: Option<DateTime<Utc>>,
+    refresh_token_expires_at: Option<DateTime<Utc>>,
+    #[builder(default)] user_type: UserType,
+    employer_tax_identification_number: Option<Afm>,
+) -> ErganiAuthenticationState {
+    ErganiAuthenticationState::builder()
+        .access_token(access_token)
+        .access_token_expired(access_token_expires_at.map_or(4102444800, |at| at.timestamp()))
+        .refresh_token("refresh-token".to_string())
+        .refresh_token_expired(
+            refresh_token_expires_at.unwrap_or_else(|| Utc::now() + TimeDelta::days(1)),
+        )
+        .user_type(user_type)
+        .maybe_employer_tax_identification_number(employer_tax_identification_number)
+        .build()
+}
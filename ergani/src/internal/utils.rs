@@ -1,14 +1,18 @@
-use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use crate::models::types::athens_time::{AthensDateTime, AthensTime, AthensTimeError};
+use chrono::{Datelike, NaiveDate};
 
-/// Formats a [DateTime] instance to `HH:MM`
+/// Formats an [AthensTime] instance to `HH:MM` on the clocks in Greece
 ///
 /// # Arguments
 /// * - `time` - The time that is going to be formatted
 ///
 /// # Returns:
 /// The formatted time
-pub(crate) fn format_time(time: &DateTime<Utc>) -> String {
-    time.format("%H:%M").to_string()
+///
+/// # Errors
+/// * - `[AthensTimeError]` - Raised if the time is skipped or repeated by a daylight saving change
+pub(crate) fn format_time(time: &AthensTime) -> Result<String, AthensTimeError> {
+    Ok(time.local()?.format("%H:%M").to_string())
 }
 
 /// Formats a [NaiveDate] instance to `dd/nm/YYYY"`
@@ -25,17 +29,22 @@ pub(crate) fn format_date(date: Option<&NaiveDate>) -> String {
     }
 }
 
-/// Formats a [DateTime] instance to an ISO 8601 format
+/// Formats an [AthensDateTime] instance to an ISO 8601 format on the clocks in Greece
 ///
 /// # Arguments
 /// * - `datetime` - The datetime that is going to be formatted
 ///
 /// # Returns:
 /// The formatted datetime
-pub(crate) fn format_datetime(datetime: Option<&DateTime<Utc>>) -> String {
+///
+/// # Errors
+/// * - `[AthensTimeError]` - Raised if the datetime is skipped or repeated by a daylight saving change
+pub(crate) fn format_datetime(
+    datetime: Option<&AthensDateTime>,
+) -> Result<String, AthensTimeError> {
     match datetime {
-        Some(datetime) => datetime.format("%Y-%m-%dT%H:%M:%S").to_string(),
-        None => "".to_string(),
+        Some(datetime) => Ok(datetime.local()?.format("%Y-%m-%dT%H:%M:%S").to_string()),
+        None => Ok("".to_string()),
     }
 }
 
@@ -74,13 +83,17 @@ pub(crate) fn get_ergani_overtime_cancellation(cancellation: bool) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{NaiveDate, Utc};
+    use chrono::{NaiveDate, NaiveTime, TimeZone, Utc};
 
     #[test]
     fn test_format_time() {
-        let dt = Utc::now();
-        let formatted_time = format_time(&dt);
-        assert_eq!(formatted_time, dt.format("%H:%M").to_string());
+        let winter = Utc.with_ymd_and_hms(2024, 1, 15, 7, 0, 0).unwrap();
+        let summer = Utc.with_ymd_and_hms(2024, 7, 15, 6, 0, 0).unwrap();
+        let local = NaiveTime::from_hms_opt(9, 0, 0).unwrap();
+
+        assert_eq!(format_time(&winter.into()).unwrap(), "09:00");
+        assert_eq!(format_time(&summer.into()).unwrap(), "09:00");
+        assert_eq!(format_time(&local.into()).unwrap(), "09:00");
     }
 
     #[test]
@@ -92,12 +105,19 @@ mod tests {
 
     #[test]
     fn test_format_datetime() {
-        let dt = Utc::now();
+        let dt = Utc.with_ymd_and_hms(2024, 3, 20, 7, 0, 0).unwrap().into();
         let formatted_datetime = format_datetime(Some(&dt));
+        assert_eq!(formatted_datetime.unwrap(), "2024-03-20T09:00:00");
+
+        let skipped = NaiveDate::from_ymd_opt(2024, 3, 31)
+            .unwrap()
+            .and_hms_opt(3, 30, 0)
+            .unwrap();
         assert_eq!(
-            formatted_datetime,
-            dt.format("%Y-%m-%dT%H:%M:%S").to_string()
+            format_datetime(Some(&skipped.into())),
+            Err(AthensTimeError::Nonexistent(skipped))
         );
+        assert_eq!(format_datetime(None).unwrap(), "");
     }
 
     #[test]
@@ -1,8 +1,21 @@
 pub mod api_error;
 pub mod auth;
 pub mod client;
+pub mod client_pool;
 mod endpoint;
+pub mod environment;
+pub mod ergani_api;
+pub mod ergani_document;
+pub mod events;
+pub mod health_check;
 mod internal;
 pub mod models;
-mod responses;
-mod ergani_fetch_response;
+pub mod prepared_request;
+pub mod responses;
+pub mod retry_policy;
+pub mod secret;
+pub mod session;
+pub mod submission_outcome;
+pub mod transport;
+pub mod validation;
+pub mod ergani_fetch_response;
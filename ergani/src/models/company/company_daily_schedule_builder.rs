@@ -2,68 +2,127 @@
 
 use crate::models::company::company_daily_schedule::CompanyDailySchedule;
 use crate::models::employee::employee_daily_schedule::EmployeeDailySchedule;
+use bon::bon;
 use chrono::NaiveDate;
 
-#[derive(Default)]
-pub struct CompanyDailyScheduleBuilder {
-    pub(crate) business_branch_number: i64,
-    pub(crate) start_date: Option<NaiveDate>,
-    pub(crate) end_date: Option<NaiveDate>,
-    pub(crate) employee_schedules: Vec<EmployeeDailySchedule>,
-    pub(crate) related_protocol_id: Option<String>,
-    pub(crate) related_protocol_date: Option<NaiveDate>,
-    pub(crate) comments: Option<String>,
+#[bon]
+impl CompanyDailySchedule {
+    /// Starts building a `CompanyDailySchedule`
+    ///
+    /// `business_branch_number` and `employee_schedules` must be set, or `build()` does not compile:
+    ///
+    /// ```compile_fail
+    /// use ergani::models::company::company_daily_schedule_builder::CompanyDailyScheduleBuilder;
+    ///
+    /// let company_daily_schedule = CompanyDailyScheduleBuilder::builder()
+    ///     .business_branch_number(0)
+    ///     // `employee_schedules` is missing
+    ///     .build();
+    /// ```
+    #[builder(state_mod = state)]
+    pub fn new(
+        business_branch_number: i64,
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        employee_schedules: Vec<EmployeeDailySchedule>,
+        #[builder(into)] related_protocol_id: Option<String>,
+        related_protocol_date: Option<NaiveDate>,
+        #[builder(into)] comments: Option<String>,
+    ) -> CompanyDailySchedule {
+        CompanyDailySchedule {
+            business_branch_number,
+            start_date,
+            end_date,
+            employee_schedules,
+            related_protocol_id,
+            related_protocol_date,
+            comments,
+        }
+    }
 }
 
 impl CompanyDailyScheduleBuilder {
+    /// Starts building a `CompanyDailySchedule`, the same as `CompanyDailySchedule::builder()`
     pub fn builder() -> CompanyDailyScheduleBuilder {
-        CompanyDailyScheduleBuilder::default()
+        CompanyDailySchedule::builder()
     }
+}
 
-    pub fn build(self) -> CompanyDailySchedule {
-        CompanyDailySchedule {
-            business_branch_number: self.business_branch_number,
-            start_date: self.start_date,
-            end_date: self.end_date,
-            employee_schedules: self.employee_schedules,
-            related_protocol_id: self.related_protocol_id,
-            related_protocol_date: self.related_protocol_date,
-            comments: self.comments,
-        }
+impl<S: state::State> CompanyDailyScheduleBuilder<S> {
+    #[deprecated(note = "use `business_branch_number`")]
+    pub fn set_business_branch_number(
+        self,
+        business_branch_number: i64,
+    ) -> CompanyDailyScheduleBuilder<state::SetBusinessBranchNumber<S>>
+    where
+        S::BusinessBranchNumber: state::IsUnset,
+    {
+        self.business_branch_number(business_branch_number)
     }
 
-    pub fn set_business_branch_number(mut self, business_branch_number: i64) -> Self {
-        self.business_branch_number = business_branch_number;
-        self
+    #[deprecated(note = "use `start_date`")]
+    pub fn set_start_date(
+        self,
+        start_date: NaiveDate,
+    ) -> CompanyDailyScheduleBuilder<state::SetStartDate<S>>
+    where
+        S::StartDate: state::IsUnset,
+    {
+        self.start_date(start_date)
     }
-    pub fn set_start_date(mut self, start_date: NaiveDate) -> Self {
-        self.start_date = Some(start_date);
-        self
-    }
-    pub fn set_end_date(mut self, end_date: NaiveDate) -> Self {
-        self.end_date = Some(end_date);
-        self
+
+    #[deprecated(note = "use `end_date`")]
+    pub fn set_end_date(
+        self,
+        end_date: NaiveDate,
+    ) -> CompanyDailyScheduleBuilder<state::SetEndDate<S>>
+    where
+        S::EndDate: state::IsUnset,
+    {
+        self.end_date(end_date)
     }
+
+    #[deprecated(note = "use `employee_schedules`")]
     pub fn set_employee_schedules(
-        mut self,
+        self,
         employee_schedules: Vec<EmployeeDailySchedule>,
-    ) -> Self {
-        self.employee_schedules = employee_schedules;
-        self
+    ) -> CompanyDailyScheduleBuilder<state::SetEmployeeSchedules<S>>
+    where
+        S::EmployeeSchedules: state::IsUnset,
+    {
+        self.employee_schedules(employee_schedules)
     }
+
+    #[deprecated(note = "use `maybe_related_protocol_id`")]
     pub fn set_related_protocol_id(
-        mut self,
+        self,
         related_protocol_id: Option<impl Into<String>>,
-    ) -> Self {
-        self.related_protocol_id = related_protocol_id.map(|s| s.into());
-        self
+    ) -> CompanyDailyScheduleBuilder<state::SetRelatedProtocolId<S>>
+    where
+        S::RelatedProtocolId: state::IsUnset,
+    {
+        self.maybe_related_protocol_id(related_protocol_id)
     }
-    pub fn set_related_protocol_date(mut self, related_protocol_date: Option<NaiveDate>) -> Self {
-        self.related_protocol_date = related_protocol_date;
-        self
+
+    #[deprecated(note = "use `maybe_related_protocol_date`")]
+    pub fn set_related_protocol_date(
+        self,
+        related_protocol_date: Option<NaiveDate>,
+    ) -> CompanyDailyScheduleBuilder<state::SetRelatedProtocolDate<S>>
+    where
+        S::RelatedProtocolDate: state::IsUnset,
+    {
+        self.maybe_related_protocol_date(related_protocol_date)
     }
-    pub fn set_comments(mut self, comments: Option<impl Into<String>>) -> Self {
-        self.comments = comments.map(|s| s.into());
-        self
+
+    #[deprecated(note = "use `maybe_comments`")]
+    pub fn set_comments(
+        self,
+        comments: Option<impl Into<String>>,
+    ) -> CompanyDailyScheduleBuilder<state::SetComments<S>>
+    where
+        S::Comments: state::IsUnset,
+    {
+        self.maybe_comments(comments)
     }
 }
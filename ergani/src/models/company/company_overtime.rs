@@ -1,5 +1,12 @@
+use crate::client::SubmissionResponse;
+use crate::endpoint::OVERTIME_ENDPOINT;
+use crate::ergani_document::ErganiDocument;
 use crate::internal::utils::format_date;
 use crate::models::overtime::Overtime;
+use crate::models::types::afm::Afm;
+use crate::validation::{
+    check_business_branch_number, check_numeric_code, join_path, Validate, ValidationIssue,
+};
 use chrono::NaiveDate;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde::Serialize as TypeSerialize;
@@ -20,13 +27,14 @@ use serde::Serialize as TypeSerialize;
 /// * - `business_secondary_activity_code_3` - Secondary activity code 3
 /// * - `business_secondary_activity_code_4` - Secondary activity code 4
 /// * - `comments` - Additional comments related to the overtime entries
+#[derive(Clone)]
 pub struct CompanyOvertime {
     pub business_branch_number: i64,
     pub sepe_service_code: String,
     pub business_primary_activity_code: String,
     pub business_branch_activity_code: String,
     pub kallikratis_municipal_code: String,
-    pub legal_representative_tax_identification_number: String,
+    pub legal_representative_tax_identification_number: Afm,
     pub employee_overtimes: Vec<Overtime>,
     pub related_protocol_id: Option<String>,
     pub related_protocol_date: Option<NaiveDate>,
@@ -129,6 +137,70 @@ impl Serialize for CompanyOvertime {
     }
 }
 
+impl ErganiDocument for CompanyOvertime {
+    const ENDPOINT: &'static str = OVERTIME_ENDPOINT;
+    const ENVELOPE_KEY: &'static str = "Overtimes";
+    const ITEM_KEY: &'static str = "Overtime";
+
+    type Response = SubmissionResponse;
+
+    fn validation_issues(&self) -> Vec<ValidationIssue> {
+        self.issues()
+    }
+}
+
+impl Validate for CompanyOvertime {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        check_business_branch_number(path, self.business_branch_number, issues);
+        check_numeric_code(path, "sepe_service_code", &self.sepe_service_code, issues);
+        check_numeric_code(
+            path,
+            "business_primary_activity_code",
+            &self.business_primary_activity_code,
+            issues,
+        );
+        check_numeric_code(
+            path,
+            "business_branch_activity_code",
+            &self.business_branch_activity_code,
+            issues,
+        );
+        check_numeric_code(
+            path,
+            "kallikratis_municipal_code",
+            &self.kallikratis_municipal_code,
+            issues,
+        );
+
+        let secondary_activity_codes = [
+            (
+                "business_secondary_activity_code_1",
+                &self.business_secondary_activity_code_1,
+            ),
+            (
+                "business_secondary_activity_code_2",
+                &self.business_secondary_activity_code_2,
+            ),
+            (
+                "business_secondary_activity_code_3",
+                &self.business_secondary_activity_code_3,
+            ),
+            (
+                "business_secondary_activity_code_4",
+                &self.business_secondary_activity_code_4,
+            ),
+        ];
+        for (field, code) in secondary_activity_codes {
+            if let Some(code) = code {
+                check_numeric_code(path, field, code, issues);
+            }
+        }
+
+        self.employee_overtimes
+            .collect_issues(&join_path(path, "employee_overtimes"), issues);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,8 +209,7 @@ mod tests {
     use crate::models::weekly_work_days::WeeklyWorkDays;
     use chrono::{DateTime, NaiveDate, Utc};
 
-    #[test]
-    fn test_serialize_company_overtime() {
+    fn company_overtime() -> CompanyOvertime {
         let date_time_start_text = "2014-11-28T12:00:09Z";
         let date_time_end_text = "2014-11-29T12:00:09Z";
 
@@ -146,13 +217,13 @@ mod tests {
         let dt_end = date_time_end_text.parse::<DateTime<Utc>>().unwrap();
 
         let overtime = Overtime {
-            employee_tax_identification_number: "123456789".to_string(),
-            employee_social_security_number: "12345678901".to_string(),
+            employee_tax_identification_number: "123456783".parse().unwrap(),
+            employee_social_security_number: "01018012342".parse().unwrap(),
             employee_last_name: "ΠΑΠΑΔΟΠΟΥΛΟΣ".to_string(),
             employee_first_name: "ΓΕΩΡΓΙΟΣ".to_string(),
             overtime_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
-            overtime_start_time: dt_start,
-            overtime_end_time: dt_end,
+            overtime_start_time: dt_start.into(),
+            overtime_end_time: dt_end.into(),
             overtime_cancellation: false,
             employee_profession_code: "1234".to_string(),
             overtime_justification:
@@ -161,13 +232,13 @@ mod tests {
             asee_approval: Some("123456".to_string()),
         };
 
-        let company_overtime = CompanyOvertime {
+        CompanyOvertime {
             business_branch_number: 1,
             sepe_service_code: "123456".to_string(),
             business_primary_activity_code: "1234".to_string(),
             business_branch_activity_code: "1234".to_string(),
             kallikratis_municipal_code: "1234".to_string(),
-            legal_representative_tax_identification_number: "123456789".to_string(),
+            legal_representative_tax_identification_number: "123456783".parse().unwrap(),
             employee_overtimes: vec![overtime],
             related_protocol_id: Some("123456".to_string()),
             related_protocol_date: Some(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap()),
@@ -177,10 +248,48 @@ mod tests {
             business_secondary_activity_code_3: None,
             business_secondary_activity_code_4: None,
             comments: None,
-        };
+        }
+    }
+
+    #[test]
+    fn test_serialize_company_overtime() {
+        let company_overtime = company_overtime();
 
         let serialized = serde_json::to_string(&company_overtime).unwrap();
         let expected_text = load_fixture_as_text("company_overtime_fixture.json");
         assert_eq!(serialized, expected_text);
     }
+
+    #[test]
+    fn test_validate_company_overtime_reports_every_issue() {
+        let mut company_overtime = company_overtime();
+        company_overtime.kallikratis_municipal_code = "".to_string();
+        company_overtime.business_secondary_activity_code_2 = Some("10.11".to_string());
+        let overtime = company_overtime.employee_overtimes[0].clone();
+        company_overtime.employee_overtimes = vec![
+            overtime.clone(),
+            Overtime {
+                overtime_start_time: overtime.overtime_end_time,
+                overtime_end_time: overtime.overtime_start_time,
+                ..overtime
+            },
+        ];
+
+        let errors = company_overtime.validate().unwrap_err();
+
+        assert_eq!(
+            errors.issues(),
+            [
+                ValidationIssue::new("kallikratis_municipal_code", "must not be empty"),
+                ValidationIssue::new(
+                    "business_secondary_activity_code_2",
+                    "must contain digits only, found \"10.11\""
+                ),
+                ValidationIssue::new(
+                    "employee_overtimes[1].overtime_end_time",
+                    "2014-11-28 14:00:09 is before overtime_start_time 2014-11-29 14:00:09"
+                ),
+            ]
+        );
+    }
 }
@@ -1,145 +1,249 @@
+#![allow(dead_code)]
+
 use crate::models::company::company_overtime::CompanyOvertime;
 use crate::models::overtime::Overtime;
+use crate::models::types::afm::parse_builder_afm;
+use anyhow::Result;
+use bon::bon;
 use chrono::NaiveDate;
 
-#[derive(Default)]
-pub struct CompanyOvertimeBuilder {
-    pub(crate) business_branch_number: i64,
-    pub(crate) sepe_service_code: String,
-    pub(crate) business_primary_activity_code: String,
-    pub(crate) business_branch_activity_code: String,
-    pub(crate) kallikratis_municipal_code: String,
-    pub(crate) legal_representative_tax_identification_number: String,
-    pub(crate) employee_overtimes: Vec<Overtime>,
-    pub(crate) related_protocol_id: Option<String>,
-    pub(crate) related_protocol_date: Option<NaiveDate>,
-    pub(crate) employer_organization: Option<String>,
-    pub(crate) business_secondary_activity_code_1: Option<String>,
-    pub(crate) business_secondary_activity_code_2: Option<String>,
-    pub(crate) business_secondary_activity_code_3: Option<String>,
-    pub(crate) business_secondary_activity_code_4: Option<String>,
-    pub(crate) comments: Option<String>,
+#[bon]
+impl CompanyOvertime {
+    /// Starts building a `CompanyOvertime`
+    ///
+    /// The branch, its codes, the legal representative and the overtimes must be set, or `build()` does not compile:
+    ///
+    /// ```compile_fail
+    /// use ergani::models::company::company_overtime_builder::CompanyOvertimeBuilder;
+    ///
+    /// let company_overtime = CompanyOvertimeBuilder::builder()
+    ///     .business_branch_number(0)
+    ///     .sepe_service_code("10000")
+    ///     .business_primary_activity_code("1234")
+    ///     .business_branch_activity_code("1234")
+    ///     .kallikratis_municipal_code("12345678")
+    ///     .legal_representative_tax_identification_number("123456783")
+    ///     // `employee_overtimes` is missing
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    /// * - `build()` fails if `legal_representative_tax_identification_number` is not a valid AFM
+    #[builder(state_mod = state)]
+    pub fn new(
+        business_branch_number: i64,
+        #[builder(into)] sepe_service_code: String,
+        #[builder(into)] business_primary_activity_code: String,
+        #[builder(into)] business_branch_activity_code: String,
+        #[builder(into)] kallikratis_municipal_code: String,
+        #[builder(into)] legal_representative_tax_identification_number: String,
+        employee_overtimes: Vec<Overtime>,
+        #[builder(into)] related_protocol_id: Option<String>,
+        related_protocol_date: Option<NaiveDate>,
+        #[builder(into)] employer_organization: Option<String>,
+        #[builder(into)] business_secondary_activity_code_1: Option<String>,
+        #[builder(into)] business_secondary_activity_code_2: Option<String>,
+        #[builder(into)] business_secondary_activity_code_3: Option<String>,
+        #[builder(into)] business_secondary_activity_code_4: Option<String>,
+        #[builder(into)] comments: Option<String>,
+    ) -> Result<CompanyOvertime> {
+        let legal_representative_tax_identification_number = parse_builder_afm(
+            "legal representative tax identification number",
+            &legal_representative_tax_identification_number,
+        )?;
+
+        Ok(CompanyOvertime {
+            business_branch_number,
+            sepe_service_code,
+            business_primary_activity_code,
+            business_branch_activity_code,
+            kallikratis_municipal_code,
+            legal_representative_tax_identification_number,
+            employee_overtimes,
+            related_protocol_id,
+            related_protocol_date,
+            employer_organization,
+            business_secondary_activity_code_1,
+            business_secondary_activity_code_2,
+            business_secondary_activity_code_3,
+            business_secondary_activity_code_4,
+            comments,
+        })
+    }
 }
 
 impl CompanyOvertimeBuilder {
+    /// Starts building a `CompanyOvertime`, the same as `CompanyOvertime::builder()`
     pub fn builder() -> CompanyOvertimeBuilder {
-        CompanyOvertimeBuilder::default()
-    }
-
-    pub fn build(self) -> CompanyOvertime {
-        CompanyOvertime {
-            business_branch_number: self.business_branch_number,
-            sepe_service_code: self.sepe_service_code,
-            business_primary_activity_code: self.business_primary_activity_code,
-            business_branch_activity_code: self.business_branch_activity_code,
-            kallikratis_municipal_code: self.kallikratis_municipal_code,
-            legal_representative_tax_identification_number: self
-                .legal_representative_tax_identification_number,
-            employee_overtimes: self.employee_overtimes,
-            related_protocol_id: self.related_protocol_id,
-            related_protocol_date: self.related_protocol_date,
-            employer_organization: self.employer_organization,
-            business_secondary_activity_code_1: self.business_secondary_activity_code_1,
-            business_secondary_activity_code_2: self.business_secondary_activity_code_2,
-            business_secondary_activity_code_3: self.business_secondary_activity_code_3,
-            business_secondary_activity_code_4: self.business_secondary_activity_code_4,
-            comments: self.comments,
-        }
-    }
-
-    pub fn set_business_branch_number(mut self, business_branch_number: i64) -> Self {
-        self.business_branch_number = business_branch_number;
-        self
-    }
-    pub fn set_sepe_service_code(mut self, sepe_service_code: impl Into<String>) -> Self {
-        self.sepe_service_code = sepe_service_code.into();
-        self
+        CompanyOvertime::builder()
+    }
+}
+
+impl<S: state::State> CompanyOvertimeBuilder<S> {
+    #[deprecated(note = "use `business_branch_number`")]
+    pub fn set_business_branch_number(
+        self,
+        business_branch_number: i64,
+    ) -> CompanyOvertimeBuilder<state::SetBusinessBranchNumber<S>>
+    where
+        S::BusinessBranchNumber: state::IsUnset,
+    {
+        self.business_branch_number(business_branch_number)
     }
+
+    #[deprecated(note = "use `sepe_service_code`")]
+    pub fn set_sepe_service_code(
+        self,
+        sepe_service_code: impl Into<String>,
+    ) -> CompanyOvertimeBuilder<state::SetSepeServiceCode<S>>
+    where
+        S::SepeServiceCode: state::IsUnset,
+    {
+        self.sepe_service_code(sepe_service_code)
+    }
+
+    #[deprecated(note = "use `business_primary_activity_code`")]
     pub fn set_business_primary_activity_code(
-        mut self,
+        self,
         business_primary_activity_code: impl Into<String>,
-    ) -> Self {
-        self.business_primary_activity_code = business_primary_activity_code.into();
-        self
+    ) -> CompanyOvertimeBuilder<state::SetBusinessPrimaryActivityCode<S>>
+    where
+        S::BusinessPrimaryActivityCode: state::IsUnset,
+    {
+        self.business_primary_activity_code(business_primary_activity_code)
     }
+
+    #[deprecated(note = "use `business_branch_activity_code`")]
     pub fn set_business_branch_activity_code(
-        mut self,
+        self,
         business_branch_activity_code: impl Into<String>,
-    ) -> Self {
-        self.business_branch_activity_code = business_branch_activity_code.into();
-        self
+    ) -> CompanyOvertimeBuilder<state::SetBusinessBranchActivityCode<S>>
+    where
+        S::BusinessBranchActivityCode: state::IsUnset,
+    {
+        self.business_branch_activity_code(business_branch_activity_code)
     }
+
+    #[deprecated(note = "use `kallikratis_municipal_code`")]
     pub fn set_kallikratis_municipal_code(
-        mut self,
+        self,
         kallikratis_municipal_code: impl Into<String>,
-    ) -> Self {
-        self.kallikratis_municipal_code = kallikratis_municipal_code.into();
-        self
+    ) -> CompanyOvertimeBuilder<state::SetKallikratisMunicipalCode<S>>
+    where
+        S::KallikratisMunicipalCode: state::IsUnset,
+    {
+        self.kallikratis_municipal_code(kallikratis_municipal_code)
     }
+
+    #[deprecated(note = "use `legal_representative_tax_identification_number`")]
     pub fn set_legal_representative_tax_identification_number(
-        mut self,
+        self,
         legal_representative_tax_identification_number: impl Into<String>,
-    ) -> Self {
-        self.legal_representative_tax_identification_number =
-            legal_representative_tax_identification_number.into();
-        self
+    ) -> CompanyOvertimeBuilder<state::SetLegalRepresentativeTaxIdentificationNumber<S>>
+    where
+        S::LegalRepresentativeTaxIdentificationNumber: state::IsUnset,
+    {
+        self.legal_representative_tax_identification_number(
+            legal_representative_tax_identification_number,
+        )
     }
-    pub fn set_employee_overtimes(mut self, employee_overtimes: Vec<Overtime>) -> Self {
-        self.employee_overtimes = employee_overtimes;
-        self
+
+    #[deprecated(note = "use `employee_overtimes`")]
+    pub fn set_employee_overtimes(
+        self,
+        employee_overtimes: Vec<Overtime>,
+    ) -> CompanyOvertimeBuilder<state::SetEmployeeOvertimes<S>>
+    where
+        S::EmployeeOvertimes: state::IsUnset,
+    {
+        self.employee_overtimes(employee_overtimes)
     }
+
+    #[deprecated(note = "use `maybe_related_protocol_id`")]
     pub fn set_related_protocol_id(
-        mut self,
+        self,
         related_protocol_id: Option<impl Into<String>>,
-    ) -> Self {
-        self.related_protocol_id = related_protocol_id.map(|s| s.into());
-        self
+    ) -> CompanyOvertimeBuilder<state::SetRelatedProtocolId<S>>
+    where
+        S::RelatedProtocolId: state::IsUnset,
+    {
+        self.maybe_related_protocol_id(related_protocol_id)
     }
-    pub fn set_related_protocol_date(mut self, related_protocol_date: Option<NaiveDate>) -> Self {
-        self.related_protocol_date = related_protocol_date;
-        self
+
+    #[deprecated(note = "use `maybe_related_protocol_date`")]
+    pub fn set_related_protocol_date(
+        self,
+        related_protocol_date: Option<NaiveDate>,
+    ) -> CompanyOvertimeBuilder<state::SetRelatedProtocolDate<S>>
+    where
+        S::RelatedProtocolDate: state::IsUnset,
+    {
+        self.maybe_related_protocol_date(related_protocol_date)
     }
+
+    #[deprecated(note = "use `maybe_employer_organization`")]
     pub fn set_employer_organization(
-        mut self,
+        self,
         employer_organization: Option<impl Into<String>>,
-    ) -> Self {
-        self.employer_organization = employer_organization.map(|s| s.into());
-        self
+    ) -> CompanyOvertimeBuilder<state::SetEmployerOrganization<S>>
+    where
+        S::EmployerOrganization: state::IsUnset,
+    {
+        self.maybe_employer_organization(employer_organization)
     }
+
+    #[deprecated(note = "use `maybe_business_secondary_activity_code_1`")]
     pub fn set_business_secondary_activity_code_1(
-        mut self,
+        self,
         business_secondary_activity_code_1: Option<impl Into<String>>,
-    ) -> Self {
-        self.business_secondary_activity_code_1 =
-            business_secondary_activity_code_1.map(|s| s.into());
-        self
+    ) -> CompanyOvertimeBuilder<state::SetBusinessSecondaryActivityCode1<S>>
+    where
+        S::BusinessSecondaryActivityCode1: state::IsUnset,
+    {
+        self.maybe_business_secondary_activity_code_1(business_secondary_activity_code_1)
     }
+
+    #[deprecated(note = "use `maybe_business_secondary_activity_code_2`")]
     pub fn set_business_secondary_activity_code_2(
-        mut self,
+        self,
         business_secondary_activity_code_2: Option<impl Into<String>>,
-    ) -> Self {
-        self.business_secondary_activity_code_2 =
-            business_secondary_activity_code_2.map(|s| s.into());
-        self
+    ) -> CompanyOvertimeBuilder<state::SetBusinessSecondaryActivityCode2<S>>
+    where
+        S::BusinessSecondaryActivityCode2: state::IsUnset,
+    {
+        self.maybe_business_secondary_activity_code_2(business_secondary_activity_code_2)
     }
+
+    #[deprecated(note = "use `maybe_business_secondary_activity_code_3`")]
     pub fn set_business_secondary_activity_code_3(
-        mut self,
+        self,
         business_secondary_activity_code_3: Option<impl Into<String>>,
-    ) -> Self {
-        self.business_secondary_activity_code_3 =
-            business_secondary_activity_code_3.map(|s| s.into());
-        self
+    ) -> CompanyOvertimeBuilder<state::SetBusinessSecondaryActivityCode3<S>>
+    where
+        S::BusinessSecondaryActivityCode3: state::IsUnset,
+    {
+        self.maybe_business_secondary_activity_code_3(business_secondary_activity_code_3)
     }
+
+    #[deprecated(note = "use `maybe_business_secondary_activity_code_4`")]
     pub fn set_business_secondary_activity_code_4(
-        mut self,
+        self,
         business_secondary_activity_code_4: Option<impl Into<String>>,
-    ) -> Self {
-        self.business_secondary_activity_code_4 =
-            business_secondary_activity_code_4.map(|s| s.into());
-        self
-    }
-    pub fn set_comments(mut self, comments: Option<impl Into<String>>) -> Self {
-        self.comments = comments.map(|s| s.into());
-        self
+    ) -> CompanyOvertimeBuilder<state::SetBusinessSecondaryActivityCode4<S>>
+    where
+        S::BusinessSecondaryActivityCode4: state::IsUnset,
+    {
+        self.maybe_business_secondary_activity_code_4(business_secondary_activity_code_4)
+    }
+
+    #[deprecated(note = "use `maybe_comments`")]
+    pub fn set_comments(
+        self,
+        comments: Option<impl Into<String>>,
+    ) -> CompanyOvertimeBuilder<state::SetComments<S>>
+    where
+        S::Comments: state::IsUnset,
+    {
+        self.maybe_comments(comments)
     }
 }
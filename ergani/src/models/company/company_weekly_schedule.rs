@@ -1,5 +1,11 @@
+use crate::client::SubmissionResponse;
+use crate::endpoint::WEEKLY_SCHEDULE_ENDPOINT;
+use crate::ergani_document::ErganiDocument;
 use crate::internal::utils::format_date;
 use crate::models::employee::employee_weekly_schedule::EmployeeWeeklySchedule;
+use crate::validation::{
+    check_business_branch_number, check_period, join_path, Validate, ValidationIssue,
+};
 use chrono::NaiveDate;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde::Serialize as TypeSerialize;
@@ -12,6 +18,7 @@ use serde::Serialize as TypeSerialize;
 /// * - `related_protocol_id` - The ID of the related protocol
 /// * - `related_protocol_date` - The date of the related protocol
 /// * - `comments` - Additional comments regarding the weekly schedule entries
+#[derive(Clone)]
 pub struct CompanyWeeklySchedule {
     pub business_branch_number: i64,
     pub start_date: NaiveDate,
@@ -70,6 +77,32 @@ impl Serialize for CompanyWeeklySchedule {
     }
 }
 
+impl ErganiDocument for CompanyWeeklySchedule {
+    const ENDPOINT: &'static str = WEEKLY_SCHEDULE_ENDPOINT;
+    const ENVELOPE_KEY: &'static str = "WTOS";
+    const ITEM_KEY: &'static str = "WTO";
+
+    type Response = SubmissionResponse;
+
+    fn validation_issues(&self) -> Vec<ValidationIssue> {
+        self.issues()
+    }
+}
+
+impl Validate for CompanyWeeklySchedule {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        check_business_branch_number(path, self.business_branch_number, issues);
+        check_period(
+            path,
+            ("start_date", &self.start_date),
+            ("end_date", &self.end_date),
+            issues,
+        );
+        self.employee_schedules
+            .collect_issues(&join_path(path, "employee_schedules"), issues);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -82,38 +115,62 @@ mod tests {
     fn test_serialize_company_weekly_schedule() {
         let employee_schedules = vec![
             EmployeeWeeklySchedule {
-                employee_tax_identification_number: "123456789".to_string(),
+                employee_tax_identification_number: "123456783".parse().unwrap(),
                 employee_last_name: "Doe".to_string(),
                 employee_first_name: "John".to_string(),
                 schedule_date: "2014-11-28".parse::<NaiveDate>().unwrap(),
                 workday_details: vec![
                     WorkDayDetails {
                         work_type: ScheduleWorkType::WorkFromHome,
-                        start_time: "2014-11-28T12:00:00Z".parse::<DateTime<Utc>>().unwrap(),
-                        end_time: "2014-11-28T16:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                        start_time: "2014-11-28T12:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
+                        end_time: "2014-11-28T16:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
                     },
                     WorkDayDetails {
                         work_type: ScheduleWorkType::WorkFromOffice,
-                        start_time: "2014-11-28T08:00:00Z".parse::<DateTime<Utc>>().unwrap(),
-                        end_time: "2014-11-28T16:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                        start_time: "2014-11-28T08:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
+                        end_time: "2014-11-28T16:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
                     },
                 ],
             },
             EmployeeWeeklySchedule {
-                employee_tax_identification_number: "987654321".to_string(),
+                employee_tax_identification_number: "987654324".parse().unwrap(),
                 employee_last_name: "Doe".to_string(),
                 employee_first_name: "Jane".to_string(),
                 schedule_date: "2014-11-28".parse::<NaiveDate>().unwrap(),
                 workday_details: vec![
                     WorkDayDetails {
                         work_type: ScheduleWorkType::WorkFromHome,
-                        start_time: "2014-11-28T12:00:00Z".parse::<DateTime<Utc>>().unwrap(),
-                        end_time: "2014-11-28T16:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                        start_time: "2014-11-28T12:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
+                        end_time: "2014-11-28T16:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
                     },
                     WorkDayDetails {
                         work_type: ScheduleWorkType::WorkFromOffice,
-                        start_time: "2014-11-28T08:00:00Z".parse::<DateTime<Utc>>().unwrap(),
-                        end_time: "2014-11-28T16:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                        start_time: "2014-11-28T08:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
+                        end_time: "2014-11-28T16:00:00Z"
+                            .parse::<DateTime<Utc>>()
+                            .unwrap()
+                            .into(),
                     },
                 ],
             },
@@ -2,68 +2,130 @@
 
 use crate::models::company::company_weekly_schedule::CompanyWeeklySchedule;
 use crate::models::employee::employee_weekly_schedule::EmployeeWeeklySchedule;
+use bon::bon;
 use chrono::NaiveDate;
 
-#[derive(Default)]
-pub struct CompanyWeeklyScheduleBuilder {
-    pub(crate) business_branch_number: i64,
-    pub(crate) start_date: NaiveDate,
-    pub(crate) end_date: NaiveDate,
-    pub(crate) employee_schedules: Vec<EmployeeWeeklySchedule>,
-    pub(crate) related_protocol_id: Option<String>,
-    pub(crate) related_protocol_date: Option<NaiveDate>,
-    pub(crate) comments: Option<String>,
+#[bon]
+impl CompanyWeeklySchedule {
+    /// Starts building a `CompanyWeeklySchedule`
+    ///
+    /// Every field but the related protocol and `comments` must be set, or `build()` does not compile:
+    ///
+    /// ```compile_fail
+    /// use chrono::NaiveDate;
+    /// use ergani::models::company::company_weekly_schedule_builder::CompanyWeeklyScheduleBuilder;
+    ///
+    /// let company_weekly_schedule = CompanyWeeklyScheduleBuilder::builder()
+    ///     .business_branch_number(0)
+    ///     .start_date(NaiveDate::from_ymd_opt(2024, 3, 18).unwrap())
+    ///     .employee_schedules(vec![])
+    ///     // `end_date` is missing
+    ///     .build();
+    /// ```
+    #[builder(state_mod = state)]
+    pub fn new(
+        business_branch_number: i64,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+        employee_schedules: Vec<EmployeeWeeklySchedule>,
+        #[builder(into)] related_protocol_id: Option<String>,
+        related_protocol_date: Option<NaiveDate>,
+        #[builder(into)] comments: Option<String>,
+    ) -> CompanyWeeklySchedule {
+        CompanyWeeklySchedule {
+            business_branch_number,
+            start_date,
+            end_date,
+            employee_schedules,
+            related_protocol_id,
+            related_protocol_date,
+            comments,
+        }
+    }
 }
 
 impl CompanyWeeklyScheduleBuilder {
+    /// Starts building a `CompanyWeeklySchedule`, the same as `CompanyWeeklySchedule::builder()`
     pub fn builder() -> CompanyWeeklyScheduleBuilder {
-        CompanyWeeklyScheduleBuilder::default()
+        CompanyWeeklySchedule::builder()
     }
+}
 
-    pub fn build(self) -> CompanyWeeklySchedule {
-        CompanyWeeklySchedule {
-            business_branch_number: self.business_branch_number,
-            start_date: self.start_date,
-            end_date: self.end_date,
-            employee_schedules: self.employee_schedules,
-            related_protocol_id: self.related_protocol_id,
-            related_protocol_date: self.related_protocol_date,
-            comments: self.comments,
-        }
+impl<S: state::State> CompanyWeeklyScheduleBuilder<S> {
+    #[deprecated(note = "use `business_branch_number`")]
+    pub fn set_business_branch_number(
+        self,
+        business_branch_number: i64,
+    ) -> CompanyWeeklyScheduleBuilder<state::SetBusinessBranchNumber<S>>
+    where
+        S::BusinessBranchNumber: state::IsUnset,
+    {
+        self.business_branch_number(business_branch_number)
     }
 
-    pub fn set_business_branch_number(mut self, business_branch_number: i64) -> Self {
-        self.business_branch_number = business_branch_number;
-        self
+    #[deprecated(note = "use `start_date`")]
+    pub fn set_start_date(
+        self,
+        start_date: NaiveDate,
+    ) -> CompanyWeeklyScheduleBuilder<state::SetStartDate<S>>
+    where
+        S::StartDate: state::IsUnset,
+    {
+        self.start_date(start_date)
     }
-    pub fn set_start_date(mut self, start_date: NaiveDate) -> Self {
-        self.start_date = start_date;
-        self
-    }
-    pub fn set_end_date(mut self, end_date: NaiveDate) -> Self {
-        self.end_date = end_date;
-        self
+
+    #[deprecated(note = "use `end_date`")]
+    pub fn set_end_date(
+        self,
+        end_date: NaiveDate,
+    ) -> CompanyWeeklyScheduleBuilder<state::SetEndDate<S>>
+    where
+        S::EndDate: state::IsUnset,
+    {
+        self.end_date(end_date)
     }
+
+    #[deprecated(note = "use `employee_schedules`")]
     pub fn set_employee_schedules(
-        mut self,
+        self,
         employee_schedules: Vec<EmployeeWeeklySchedule>,
-    ) -> Self {
-        self.employee_schedules = employee_schedules;
-        self
+    ) -> CompanyWeeklyScheduleBuilder<state::SetEmployeeSchedules<S>>
+    where
+        S::EmployeeSchedules: state::IsUnset,
+    {
+        self.employee_schedules(employee_schedules)
     }
+
+    #[deprecated(note = "use `maybe_related_protocol_id`")]
     pub fn set_related_protocol_id(
-        mut self,
+        self,
         related_protocol_id: Option<impl Into<String>>,
-    ) -> Self {
-        self.related_protocol_id = related_protocol_id.map(|s| s.into());
-        self
+    ) -> CompanyWeeklyScheduleBuilder<state::SetRelatedProtocolId<S>>
+    where
+        S::RelatedProtocolId: state::IsUnset,
+    {
+        self.maybe_related_protocol_id(related_protocol_id)
     }
-    pub fn set_related_protocol_date(mut self, related_protocol_date: Option<NaiveDate>) -> Self {
-        self.related_protocol_date = related_protocol_date;
-        self
+
+    #[deprecated(note = "use `maybe_related_protocol_date`")]
+    pub fn set_related_protocol_date(
+        self,
+        related_protocol_date: Option<NaiveDate>,
+    ) -> CompanyWeeklyScheduleBuilder<state::SetRelatedProtocolDate<S>>
+    where
+        S::RelatedProtocolDate: state::IsUnset,
+    {
+        self.maybe_related_protocol_date(related_protocol_date)
     }
-    pub fn set_comments(mut self, comments: Option<impl Into<String>>) -> Self {
-        self.comments = comments.map(|s| s.into());
-        self
+
+    #[deprecated(note = "use `maybe_comments`")]
+    pub fn set_comments(
+        self,
+        comments: Option<impl Into<String>>,
+    ) -> CompanyWeeklyScheduleBuilder<state::SetComments<S>>
+    where
+        S::Comments: state::IsUnset,
+    {
+        self.maybe_comments(comments)
     }
 }
@@ -1,4 +1,9 @@
+use crate::client::SubmissionResponse;
+use crate::endpoint::WORK_CARD_ENDPOINT;
+use crate::ergani_document::ErganiDocument;
+use crate::models::types::afm::Afm;
 use crate::models::work_card::WorkCard;
+use crate::validation::{check_business_branch_number, join_path, Validate, ValidationIssue};
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde::Serialize as TypeSerialize;
 
@@ -7,8 +12,9 @@ use serde::Serialize as TypeSerialize;
 /// * - `business_branch_number` - The number identifying the specific business branch
 /// * - `comments` - Additional comments related to the work cards
 /// * - `card_details` - A list of `WorkCard` entries for the business branch
+#[derive(Clone)]
 pub struct CompanyWorkCard {
-    pub employer_tax_identification_number: String,
+    pub employer_tax_identification_number: Afm,
     pub business_branch_number: i64,
     pub comments: Option<String>,
     pub card_details: Vec<WorkCard>,
@@ -44,6 +50,30 @@ impl Serialize for CompanyWorkCard {
     }
 }
 
+impl ErganiDocument for CompanyWorkCard {
+    const ENDPOINT: &'static str = WORK_CARD_ENDPOINT;
+    const ENVELOPE_KEY: &'static str = "Cards";
+    const ITEM_KEY: &'static str = "Card";
+
+    type Response = SubmissionResponse;
+
+    fn employer_tax_identification_number(&self) -> Option<&Afm> {
+        Some(&self.employer_tax_identification_number)
+    }
+
+    fn validation_issues(&self) -> Vec<ValidationIssue> {
+        self.issues()
+    }
+}
+
+impl Validate for CompanyWorkCard {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        check_business_branch_number(path, self.business_branch_number, issues);
+        self.card_details
+            .collect_issues(&join_path(path, "card_details"), issues);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -57,17 +87,17 @@ mod tests {
         let dt = date_time.parse::<DateTime<Utc>>().unwrap();
 
         let work_card = WorkCard {
-            employee_tax_identification_number: "123456789".to_string(),
+            employee_tax_identification_number: "123456783".parse().unwrap(),
             employee_last_name: "ΠΑΠΑΔΟΠΟΥΛΟΣ".to_string(),
             employee_first_name: "ΓΕΩΡΓΙΟΣ".to_string(),
             work_card_submission_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
-            work_card_movement_datetime: dt,
+            work_card_movement_datetime: dt.into(),
             late_declaration_justification: None,
             work_card_movement_type: WorkCardMovementType::Departure,
         };
 
         let company_work_card = CompanyWorkCard {
-            employer_tax_identification_number: "987654321".to_string(),
+            employer_tax_identification_number: "987654324".parse().unwrap(),
             business_branch_number: 1,
             comments: Some("Σχόλια".to_string()),
             card_details: vec![work_card],
@@ -77,4 +107,47 @@ mod tests {
         let expected = load_fixture_as_text("company_work_card_fixture.json");
         assert_eq!(serialized, expected);
     }
+
+    #[test]
+    fn test_validate_company_work_card_reports_every_issue() {
+        let dt = "2014-11-28T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let work_card = |last_name: &str, first_name: &str| WorkCard {
+            employee_tax_identification_number: "123456783".parse().unwrap(),
+            employee_last_name: last_name.to_string(),
+            employee_first_name: first_name.to_string(),
+            work_card_submission_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
+            work_card_movement_datetime: dt.into(),
+            late_declaration_justification: None,
+            work_card_movement_type: WorkCardMovementType::Arrival,
+        };
+
+        let company_work_card = CompanyWorkCard {
+            employer_tax_identification_number: "987654324".parse().unwrap(),
+            business_branch_number: -1,
+            comments: None,
+            card_details: vec![
+                work_card("ΠΑΠΑΔΟΠΟΥΛΟΣ", "ΓΕΩΡΓΙΟΣ"),
+                work_card("ΠΑΠΑΔΟΠΟΥΛΟΣ", " "),
+                work_card("", ""),
+            ],
+        };
+
+        let paths = company_work_card
+            .validate()
+            .unwrap_err()
+            .issues()
+            .iter()
+            .map(|issue| issue.path().to_string())
+            .collect::<Vec<String>>();
+
+        assert_eq!(
+            paths,
+            [
+                "business_branch_number",
+                "card_details[1].employee_first_name",
+                "card_details[2].employee_last_name",
+                "card_details[2].employee_first_name",
+            ]
+        );
+    }
 }
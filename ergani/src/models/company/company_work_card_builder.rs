@@ -1,47 +1,99 @@
 #![allow(dead_code)]
 
 use crate::models::company::company_work_card::CompanyWorkCard;
+use crate::models::types::afm::parse_builder_afm;
 use crate::models::work_card::WorkCard;
+use anyhow::Result;
+use bon::bon;
 
-#[derive(Default)]
-pub struct CompanyWorkCardBuilder {
-    pub(crate) employer_tax_identification_number: String,
-    pub(crate) business_branch_number: i64,
-    pub(crate) comments: Option<String>,
-    pub(crate) card_details: Vec<WorkCard>,
+#[bon]
+impl CompanyWorkCard {
+    /// Starts building a `CompanyWorkCard`
+    ///
+    /// Every field but `comments` must be set, or `build()` does not compile:
+    ///
+    /// ```compile_fail
+    /// use ergani::models::company::company_work_card_builder::CompanyWorkCardBuilder;
+    ///
+    /// let company_work_card = CompanyWorkCardBuilder::builder()
+    ///     .employer_tax_identification_number("123456783")
+    ///     .business_branch_number(0)
+    ///     // `card_details` is missing
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    /// * - `build()` fails if `employer_tax_identification_number` is not a valid AFM
+    #[builder(state_mod = state)]
+    pub fn new(
+        #[builder(into)] employer_tax_identification_number: String,
+        business_branch_number: i64,
+        #[builder(into)] comments: Option<String>,
+        card_details: Vec<WorkCard>,
+    ) -> Result<CompanyWorkCard> {
+        let employer_tax_identification_number = parse_builder_afm(
+            "employer tax identification number",
+            &employer_tax_identification_number,
+        )?;
+
+        Ok(CompanyWorkCard {
+            employer_tax_identification_number,
+            business_branch_number,
+            comments,
+            card_details,
+        })
+    }
 }
 
 impl CompanyWorkCardBuilder {
+    /// Starts building a `CompanyWorkCard`, the same as `CompanyWorkCard::builder()`
     pub fn builder() -> CompanyWorkCardBuilder {
-        CompanyWorkCardBuilder::default()
-    }
-
-    pub fn build(self) -> CompanyWorkCard {
-        CompanyWorkCard {
-            employer_tax_identification_number: self.employer_tax_identification_number,
-            business_branch_number: self.business_branch_number,
-            comments: self.comments,
-            card_details: self.card_details,
-        }
+        CompanyWorkCard::builder()
     }
+}
 
+impl<S: state::State> CompanyWorkCardBuilder<S> {
+    #[deprecated(note = "use `employer_tax_identification_number`")]
     pub fn set_employer_tax_identification_number(
-        mut self,
+        self,
         employer_tax_identification_number: impl Into<String>,
-    ) -> Self {
-        self.employer_tax_identification_number = employer_tax_identification_number.into();
-        self
+    ) -> CompanyWorkCardBuilder<state::SetEmployerTaxIdentificationNumber<S>>
+    where
+        S::EmployerTaxIdentificationNumber: state::IsUnset,
+    {
+        self.employer_tax_identification_number(employer_tax_identification_number)
     }
-    pub fn set_business_branch_number(mut self, business_branch_number: i64) -> Self {
-        self.business_branch_number = business_branch_number;
-        self
+
+    #[deprecated(note = "use `business_branch_number`")]
+    pub fn set_business_branch_number(
+        self,
+        business_branch_number: i64,
+    ) -> CompanyWorkCardBuilder<state::SetBusinessBranchNumber<S>>
+    where
+        S::BusinessBranchNumber: state::IsUnset,
+    {
+        self.business_branch_number(business_branch_number)
     }
-    pub fn set_comments(mut self, comments: Option<impl Into<String>>) -> Self {
-        self.comments = comments.map(|s| s.into());
-        self
+
+    #[deprecated(note = "use `maybe_comments`")]
+    pub fn set_comments(
+        self,
+        comments: Option<impl Into<String>>,
+    ) -> CompanyWorkCardBuilder<state::SetComments<S>>
+    where
+        S::Comments: state::IsUnset,
+    {
+        self.maybe_comments(comments)
     }
-    pub fn set_card_details(mut self, card_details: Vec<WorkCard>) -> Self {
-        self.card_details = card_details;
-        self
+
+    #[deprecated(note = "use `card_details`")]
+    pub fn set_card_details(
+        self,
+        card_details: Vec<WorkCard>,
+    ) -> CompanyWorkCardBuilder<state::SetCardDetails<S>>
+    where
+        S::CardDetails: state::IsUnset,
+    {
+        self.card_details(card_details)
     }
 }
@@ -1,8 +1,8 @@
-pub(crate) mod company_daily_schedule;
+pub mod company_daily_schedule;
 pub mod company_daily_schedule_builder;
-pub(crate) mod company_overtime;
+pub mod company_overtime;
 pub mod company_overtime_builder;
-pub(crate) mod company_weekly_schedule;
+pub mod company_weekly_schedule;
 pub mod company_weekly_schedule_builder;
-pub(crate) mod company_work_card;
+pub mod company_work_card;
 pub mod company_work_card_builder;
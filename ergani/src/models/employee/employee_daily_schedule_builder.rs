@@ -1,54 +1,116 @@
 #![allow(dead_code)]
 
 use crate::models::employee::employee_daily_schedule::EmployeeDailySchedule;
+use crate::models::types::afm::parse_builder_afm;
 use crate::models::work_day_details::WorkDayDetails;
+use anyhow::Result;
+use bon::bon;
 use chrono::NaiveDate;
 
-#[derive(Default)]
-pub struct EmployeeDailyScheduleBuilder {
-    pub(crate) employee_tax_identification_number: String,
-    pub(crate) employee_last_name: String,
-    pub(crate) employee_first_name: String,
-    pub(crate) schedule_date: NaiveDate,
-    pub(crate) workday_details: Vec<WorkDayDetails>,
+#[bon]
+impl EmployeeDailySchedule {
+    /// Starts building an `EmployeeDailySchedule`
+    ///
+    /// Every field must be set, or `build()` does not compile:
+    ///
+    /// ```compile_fail
+    /// use chrono::NaiveDate;
+    /// use ergani::models::employee::employee_daily_schedule_builder::EmployeeDailyScheduleBuilder;
+    ///
+    /// let employee_daily_schedule = EmployeeDailyScheduleBuilder::builder()
+    ///     .employee_tax_identification_number("123456783")
+    ///     .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+    ///     .employee_first_name("ΓΕΩΡΓΙΟΣ")
+    ///     .schedule_date(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap())
+    ///     // `workday_details` is missing
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    /// * - `build()` fails if `employee_tax_identification_number` is not a valid AFM
+    #[builder(state_mod = state)]
+    pub fn new(
+        #[builder(into)] employee_tax_identification_number: String,
+        #[builder(into)] employee_last_name: String,
+        #[builder(into)] employee_first_name: String,
+        schedule_date: NaiveDate,
+        workday_details: Vec<WorkDayDetails>,
+    ) -> Result<EmployeeDailySchedule> {
+        let employee_tax_identification_number = parse_builder_afm(
+            "employee tax identification number",
+            &employee_tax_identification_number,
+        )?;
+
+        Ok(EmployeeDailySchedule {
+            employee_tax_identification_number,
+            employee_last_name,
+            employee_first_name,
+            schedule_date,
+            workday_details,
+        })
+    }
 }
 
 impl EmployeeDailyScheduleBuilder {
+    /// Starts building an `EmployeeDailySchedule`, the same as `EmployeeDailySchedule::builder()`
     pub fn builder() -> EmployeeDailyScheduleBuilder {
-        EmployeeDailyScheduleBuilder::default()
-    }
-
-    pub fn build(self) -> EmployeeDailySchedule {
-        EmployeeDailySchedule {
-            employee_tax_identification_number: self.employee_tax_identification_number,
-            employee_last_name: self.employee_last_name,
-            employee_first_name: self.employee_first_name,
-            schedule_date: self.schedule_date,
-            workday_details: self.workday_details,
-        }
+        EmployeeDailySchedule::builder()
     }
+}
 
+impl<S: state::State> EmployeeDailyScheduleBuilder<S> {
+    #[deprecated(note = "use `employee_tax_identification_number`")]
     pub fn set_employee_tax_identification_number(
-        mut self,
+        self,
         employee_tax_identification_number: impl Into<String>,
-    ) -> Self {
-        self.employee_tax_identification_number = employee_tax_identification_number.into();
-        self
+    ) -> EmployeeDailyScheduleBuilder<state::SetEmployeeTaxIdentificationNumber<S>>
+    where
+        S::EmployeeTaxIdentificationNumber: state::IsUnset,
+    {
+        self.employee_tax_identification_number(employee_tax_identification_number)
     }
-    pub fn set_employee_last_name(mut self, employee_last_name: impl Into<String>) -> Self {
-        self.employee_last_name = employee_last_name.into();
-        self
+
+    #[deprecated(note = "use `employee_last_name`")]
+    pub fn set_employee_last_name(
+        self,
+        employee_last_name: impl Into<String>,
+    ) -> EmployeeDailyScheduleBuilder<state::SetEmployeeLastName<S>>
+    where
+        S::EmployeeLastName: state::IsUnset,
+    {
+        self.employee_last_name(employee_last_name)
     }
-    pub fn set_employee_first_name(mut self, employee_first_name: impl Into<String>) -> Self {
-        self.employee_first_name = employee_first_name.into();
-        self
+
+    #[deprecated(note = "use `employee_first_name`")]
+    pub fn set_employee_first_name(
+        self,
+        employee_first_name: impl Into<String>,
+    ) -> EmployeeDailyScheduleBuilder<state::SetEmployeeFirstName<S>>
+    where
+        S::EmployeeFirstName: state::IsUnset,
+    {
+        self.employee_first_name(employee_first_name)
     }
-    pub fn set_schedule_date(mut self, schedule_date: NaiveDate) -> Self {
-        self.schedule_date = schedule_date;
-        self
+
+    #[deprecated(note = "use `schedule_date`")]
+    pub fn set_schedule_date(
+        self,
+        schedule_date: NaiveDate,
+    ) -> EmployeeDailyScheduleBuilder<state::SetScheduleDate<S>>
+    where
+        S::ScheduleDate: state::IsUnset,
+    {
+        self.schedule_date(schedule_date)
     }
-    pub fn set_workday_details(mut self, workday_details: Vec<WorkDayDetails>) -> Self {
-        self.workday_details = workday_details;
-        self
+
+    #[deprecated(note = "use `workday_details`")]
+    pub fn set_workday_details(
+        self,
+        workday_details: Vec<WorkDayDetails>,
+    ) -> EmployeeDailyScheduleBuilder<state::SetWorkdayDetails<S>>
+    where
+        S::WorkdayDetails: state::IsUnset,
+    {
+        self.workday_details(workday_details)
     }
 }
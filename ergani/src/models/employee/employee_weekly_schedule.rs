@@ -1,5 +1,7 @@
 use crate::internal::utils::get_day_of_week;
+use crate::models::types::afm::Afm;
 use crate::models::work_day_details::WorkDayDetails;
+use crate::validation::{check_not_blank, join_path, Validate, ValidationIssue};
 use chrono::NaiveDate;
 use serde::ser::{Serialize, SerializeStruct, Serializer};
 use serde::Serialize as TypeSerialize;
@@ -12,7 +14,7 @@ use serde::Serialize as TypeSerialize;
 /// * - `workday_details` - A list of workday detail entries for the week
 #[derive(Clone)]
 pub struct EmployeeWeeklySchedule {
-    pub employee_tax_identification_number: String,
+    pub employee_tax_identification_number: Afm,
     pub employee_last_name: String,
     pub employee_first_name: String,
     pub schedule_date: NaiveDate,
@@ -49,6 +51,20 @@ impl Serialize for EmployeeWeeklySchedule {
     }
 }
 
+impl Validate for EmployeeWeeklySchedule {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        check_not_blank(path, "employee_last_name", &self.employee_last_name, issues);
+        check_not_blank(
+            path,
+            "employee_first_name",
+            &self.employee_first_name,
+            issues,
+        );
+        self.workday_details
+            .collect_issues(&join_path(path, "workday_details"), issues);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,18 +77,30 @@ mod tests {
         let workday_details = vec![
             WorkDayDetails {
                 work_type: ScheduleWorkType::WorkFromHome,
-                start_time: "2014-11-28T12:00:00Z".parse::<DateTime<Utc>>().unwrap(),
-                end_time: "2014-11-28T16:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                start_time: "2014-11-28T12:00:00Z"
+                    .parse::<DateTime<Utc>>()
+                    .unwrap()
+                    .into(),
+                end_time: "2014-11-28T16:00:00Z"
+                    .parse::<DateTime<Utc>>()
+                    .unwrap()
+                    .into(),
             },
             WorkDayDetails {
                 work_type: ScheduleWorkType::WorkFromOffice,
-                start_time: "2014-11-28T08:00:00Z".parse::<DateTime<Utc>>().unwrap(),
-                end_time: "2014-11-28T16:00:00Z".parse::<DateTime<Utc>>().unwrap(),
+                start_time: "2014-11-28T08:00:00Z"
+                    .parse::<DateTime<Utc>>()
+                    .unwrap()
+                    .into(),
+                end_time: "2014-11-28T16:00:00Z"
+                    .parse::<DateTime<Utc>>()
+                    .unwrap()
+                    .into(),
             },
         ];
 
         let employee_weekly_schedule = EmployeeWeeklySchedule {
-            employee_tax_identification_number: "123456789".to_string(),
+            employee_tax_identification_number: "123456783".parse().unwrap(),
             employee_last_name: "Παπαδόπουλος".to_string(),
             employee_first_name: "Γιάννης".to_string(),
             schedule_date: "2024-03-17".parse::<NaiveDate>().unwrap(),
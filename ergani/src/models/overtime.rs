@@ -1,8 +1,12 @@
 use crate::internal::utils::{format_date, format_time, get_ergani_overtime_cancellation};
+use crate::models::types::afm::Afm;
+use crate::models::types::amka::Amka;
+use crate::models::types::athens_time::AthensTime;
 use crate::models::types::overtime_justification_type::OvertimeJustificationType;
 use crate::models::weekly_work_days::WeeklyWorkDays;
-use chrono::{DateTime, NaiveDate, Utc};
-use serde::ser::{Serialize, SerializeStruct, Serializer};
+use crate::validation::{check_not_blank, check_time_period, Validate, ValidationIssue};
+use chrono::NaiveDate;
+use serde::ser::{Error, Serialize, SerializeStruct, Serializer};
 
 /// Represents an overtime entry for an employee
 /// * - `employee_tax_identification_number` - The employee's tax identification number
@@ -10,8 +14,8 @@ use serde::ser::{Serialize, SerializeStruct, Serializer};
 /// * - `employee_last_name` - The last name of the employee
 /// * - `employee_first_name` - The first name of the employee
 /// * - `overtime_date` - The date of the overtime
-/// * - `overtime_start_time` - The start time of the overtime period
-/// * - `overtime_end_time` - The end time of the overtime period
+/// * - `overtime_start_time` - The start time of the overtime period, sent as the time in Greece
+/// * - `overtime_end_time` - The end time of the overtime period, sent as the time in Greece
 /// * - `overtime_cancellation` - Indicates if the overtime was cancelled or not
 /// * - `employee_profession_code` - The profession code of the employee
 /// * - `overtime_justification` - The justification for the overtime
@@ -19,13 +23,13 @@ use serde::ser::{Serialize, SerializeStruct, Serializer};
 /// * - `asee_approval` - The ASEE aproval
 #[derive(Clone)]
 pub struct Overtime {
-    pub employee_tax_identification_number: String,
-    pub employee_social_security_number: String,
+    pub employee_tax_identification_number: Afm,
+    pub employee_social_security_number: Amka,
     pub employee_last_name: String,
     pub employee_first_name: String,
     pub overtime_date: NaiveDate,
-    pub overtime_start_time: DateTime<Utc>,
-    pub overtime_end_time: DateTime<Utc>,
+    pub overtime_start_time: AthensTime,
+    pub overtime_end_time: AthensTime,
     pub overtime_cancellation: bool,
     pub employee_profession_code: String,
     pub overtime_justification: OvertimeJustificationType,
@@ -39,14 +43,17 @@ impl Serialize for Overtime {
         S: Serializer,
     {
         let overtime_cancellation = get_ergani_overtime_cancellation(self.overtime_cancellation);
+        let overtime_start_time =
+            format_time(&self.overtime_start_time).map_err(S::Error::custom)?;
+        let overtime_end_time = format_time(&self.overtime_end_time).map_err(S::Error::custom)?;
         let mut overtime = serializer.serialize_struct("Overtime", 11)?;
         overtime.serialize_field("f_afm", &self.employee_tax_identification_number)?;
         overtime.serialize_field("f_amka", &self.employee_social_security_number)?;
         overtime.serialize_field("f_eponymo", &self.employee_last_name)?;
         overtime.serialize_field("f_onoma", &self.employee_first_name)?;
         overtime.serialize_field("f_date", &format_date(Some(&self.overtime_date)))?;
-        overtime.serialize_field("f_from", &format_time(&self.overtime_start_time))?;
-        overtime.serialize_field("f_to", &format_time(&self.overtime_end_time))?;
+        overtime.serialize_field("f_from", &overtime_start_time)?;
+        overtime.serialize_field("f_to", &overtime_end_time)?;
         overtime.serialize_field("f_cancellation", &overtime_cancellation)?;
         overtime.serialize_field("f_step", &self.employee_profession_code)?;
         overtime.serialize_field("f_reason", &self.overtime_justification.value())?;
@@ -56,10 +63,35 @@ impl Serialize for Overtime {
     }
 }
 
+impl Validate for Overtime {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        check_not_blank(path, "employee_last_name", &self.employee_last_name, issues);
+        check_not_blank(
+            path,
+            "employee_first_name",
+            &self.employee_first_name,
+            issues,
+        );
+        check_not_blank(
+            path,
+            "employee_profession_code",
+            &self.employee_profession_code,
+            issues,
+        );
+        check_time_period(
+            path,
+            ("overtime_start_time", &self.overtime_start_time),
+            ("overtime_end_time", &self.overtime_end_time),
+            issues,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::internal::tests::load_fixture_as_text;
+    use chrono::{DateTime, Utc};
 
     #[test]
     fn test_serialize_overtime() {
@@ -70,13 +102,13 @@ mod tests {
         let dt_end = date_time_end_text.parse::<DateTime<Utc>>().unwrap();
 
         let overtime = Overtime {
-            employee_tax_identification_number: "123456789".to_string(),
-            employee_social_security_number: "12345678901".to_string(),
+            employee_tax_identification_number: "123456783".parse().unwrap(),
+            employee_social_security_number: "01018012342".parse().unwrap(),
             employee_last_name: "ΠΑΠΑΔΟΠΟΥΛΟΣ".to_string(),
             employee_first_name: "ΓΕΩΡΓΙΟΣ".to_string(),
             overtime_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
-            overtime_start_time: dt_start,
-            overtime_end_time: dt_end,
+            overtime_start_time: dt_start.into(),
+            overtime_end_time: dt_end.into(),
             overtime_cancellation: false,
             employee_profession_code: "1234".to_string(),
             overtime_justification:
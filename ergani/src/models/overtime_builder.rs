@@ -1,113 +1,226 @@
 #![allow(dead_code)]
 
 use crate::models::overtime::Overtime;
+use crate::models::types::afm::parse_builder_afm;
+use crate::models::types::amka::parse_builder_amka;
+use crate::models::types::athens_time::AthensTime;
 use crate::models::types::overtime_justification_type::OvertimeJustificationType;
 use crate::models::weekly_work_days::WeeklyWorkDays;
-use anyhow::{bail, Result};
-use chrono::{DateTime, NaiveDate, Utc};
-
-#[derive(Default)]
-pub struct OvertimeBuilder {
-    pub(crate) employee_tax_identification_number: String,
-    pub(crate) employee_social_security_number: String,
-    pub(crate) employee_last_name: String,
-    pub(crate) employee_first_name: String,
-    pub(crate) overtime_date: NaiveDate,
-    pub(crate) overtime_start_time: DateTime<Utc>,
-    pub(crate) overtime_end_time: DateTime<Utc>,
-    pub(crate) overtime_cancellation: bool,
-    pub(crate) employee_profession_code: String,
-    pub(crate) overtime_justification: Option<OvertimeJustificationType>,
-    pub(crate) weekly_workdays_number: WeeklyWorkDays,
-    pub(crate) asee_approval: Option<String>,
-}
-
-impl OvertimeBuilder {
-    pub fn builder() -> OvertimeBuilder {
-        OvertimeBuilder::default()
-    }
+use anyhow::Result;
+use bon::bon;
+use chrono::NaiveDate;
 
-    pub fn build(self) -> Result<Overtime> {
-        let overtime_justification = match self.overtime_justification {
-            Some(ot) => ot,
-            None => bail!("Overtime justification is required"),
-        };
+#[bon]
+impl Overtime {
+    /// Starts building an `Overtime`
+    ///
+    /// The times take any [DateTime](chrono::DateTime), or a [NaiveTime](chrono::NaiveTime) or
+    /// [NaiveDateTime](chrono::NaiveDateTime) on the clocks in Greece.
+    ///
+    /// Every field but `overtime_cancellation`, which defaults to `false`, and `asee_approval` must be set, or `build()`
+    /// does not compile:
+    ///
+    /// ```compile_fail
+    /// use chrono::{NaiveDate, TimeZone, Utc};
+    /// use ergani::models::overtime_builder::OvertimeBuilder;
+    /// use ergani::models::weekly_work_days::WeeklyWorkDays;
+    ///
+    /// let overtime = OvertimeBuilder::builder()
+    ///     .employee_tax_identification_number("123456783")
+    ///     .employee_social_security_number("01018012342")
+    ///     .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+    ///     .employee_first_name("ΓΕΩΡΓΙΟΣ")
+    ///     .overtime_date(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap())
+    ///     .overtime_start_time(Utc.with_ymd_and_hms(2024, 3, 20, 16, 0, 0).unwrap())
+    ///     .overtime_end_time(Utc.with_ymd_and_hms(2024, 3, 20, 18, 0, 0).unwrap())
+    ///     .employee_profession_code("1234")
+    ///     .weekly_workdays_number(WeeklyWorkDays::Five)
+    ///     // `overtime_justification` is missing
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    /// * - `build()` fails if `employee_tax_identification_number` is not a valid AFM or
+    ///   `employee_social_security_number` is not a valid AMKA
+    #[builder(state_mod = state)]
+    pub fn new(
+        #[builder(into)] employee_tax_identification_number: String,
+        #[builder(into)] employee_social_security_number: String,
+        #[builder(into)] employee_last_name: String,
+        #[builder(into)] employee_first_name: String,
+        overtime_date: NaiveDate,
+        #[builder(into)] overtime_start_time: AthensTime,
+        #[builder(into)] overtime_end_time: AthensTime,
+        #[builder(default)] overtime_cancellation: bool,
+        #[builder(into)] employee_profession_code: String,
+        overtime_justification: OvertimeJustificationType,
+        weekly_workdays_number: WeeklyWorkDays,
+        #[builder(into)] asee_approval: Option<String>,
+    ) -> Result<Overtime> {
+        let employee_tax_identification_number = parse_builder_afm(
+            "employee tax identification number",
+            &employee_tax_identification_number,
+        )?;
+        let employee_social_security_number = parse_builder_amka(
+            "employee social security number",
+            &employee_social_security_number,
+        )?;
 
         Ok(Overtime {
-            employee_tax_identification_number: self.employee_tax_identification_number,
-            employee_social_security_number: self.employee_social_security_number,
-            employee_last_name: self.employee_last_name,
-            employee_first_name: self.employee_first_name,
-            overtime_date: self.overtime_date,
-            overtime_start_time: self.overtime_start_time,
-            overtime_end_time: self.overtime_end_time,
-            overtime_cancellation: self.overtime_cancellation,
-            employee_profession_code: self.employee_profession_code,
-            weekly_workdays_number: self.weekly_workdays_number,
-            asee_approval: self.asee_approval,
+            employee_tax_identification_number,
+            employee_social_security_number,
+            employee_last_name,
+            employee_first_name,
+            overtime_date,
+            overtime_start_time,
+            overtime_end_time,
+            overtime_cancellation,
+            employee_profession_code,
             overtime_justification,
+            weekly_workdays_number,
+            asee_approval,
         })
     }
+}
 
+impl OvertimeBuilder {
+    /// Starts building an `Overtime`
+    pub fn builder() -> OvertimeBuilder {
+        Overtime::builder()
+    }
+}
+
+impl<S: state::State> OvertimeBuilder<S> {
+    #[deprecated(note = "use `employee_tax_identification_number`")]
     pub fn set_employee_tax_identification_number(
-        mut self,
+        self,
         employee_tax_identification_number: impl Into<String>,
-    ) -> Self {
-        self.employee_tax_identification_number = employee_tax_identification_number.into();
-        self
+    ) -> OvertimeBuilder<state::SetEmployeeTaxIdentificationNumber<S>>
+    where
+        S::EmployeeTaxIdentificationNumber: state::IsUnset,
+    {
+        self.employee_tax_identification_number(employee_tax_identification_number)
     }
+
+    #[deprecated(note = "use `employee_social_security_number`")]
     pub fn set_employee_social_security_number(
-        mut self,
+        self,
         employee_social_security_number: impl Into<String>,
-    ) -> Self {
-        self.employee_social_security_number = employee_social_security_number.into();
-        self
+    ) -> OvertimeBuilder<state::SetEmployeeSocialSecurityNumber<S>>
+    where
+        S::EmployeeSocialSecurityNumber: state::IsUnset,
+    {
+        self.employee_social_security_number(employee_social_security_number)
     }
-    pub fn set_employee_last_name(mut self, employee_last_name: impl Into<String>) -> Self {
-        self.employee_last_name = employee_last_name.into();
-        self
+
+    #[deprecated(note = "use `employee_last_name`")]
+    pub fn set_employee_last_name(
+        self,
+        employee_last_name: impl Into<String>,
+    ) -> OvertimeBuilder<state::SetEmployeeLastName<S>>
+    where
+        S::EmployeeLastName: state::IsUnset,
+    {
+        self.employee_last_name(employee_last_name)
     }
-    pub fn set_employee_first_name(mut self, employee_first_name: impl Into<String>) -> Self {
-        self.employee_first_name = employee_first_name.into();
-        self
+
+    #[deprecated(note = "use `employee_first_name`")]
+    pub fn set_employee_first_name(
+        self,
+        employee_first_name: impl Into<String>,
+    ) -> OvertimeBuilder<state::SetEmployeeFirstName<S>>
+    where
+        S::EmployeeFirstName: state::IsUnset,
+    {
+        self.employee_first_name(employee_first_name)
     }
-    pub fn set_overtime_date(mut self, overtime_date: NaiveDate) -> Self {
-        self.overtime_date = overtime_date;
-        self
+
+    #[deprecated(note = "use `overtime_date`")]
+    pub fn set_overtime_date(
+        self,
+        overtime_date: NaiveDate,
+    ) -> OvertimeBuilder<state::SetOvertimeDate<S>>
+    where
+        S::OvertimeDate: state::IsUnset,
+    {
+        self.overtime_date(overtime_date)
     }
-    pub fn set_overtime_start_time(mut self, overtime_start_time: DateTime<Utc>) -> Self {
-        self.overtime_start_time = overtime_start_time;
-        self
+
+    #[deprecated(note = "use `overtime_start_time`")]
+    pub fn set_overtime_start_time(
+        self,
+        overtime_start_time: impl Into<AthensTime>,
+    ) -> OvertimeBuilder<state::SetOvertimeStartTime<S>>
+    where
+        S::OvertimeStartTime: state::IsUnset,
+    {
+        self.overtime_start_time(overtime_start_time)
     }
-    pub fn set_overtime_end_time(mut self, overtime_end_time: DateTime<Utc>) -> Self {
-        self.overtime_end_time = overtime_end_time;
-        self
+
+    #[deprecated(note = "use `overtime_end_time`")]
+    pub fn set_overtime_end_time(
+        self,
+        overtime_end_time: impl Into<AthensTime>,
+    ) -> OvertimeBuilder<state::SetOvertimeEndTime<S>>
+    where
+        S::OvertimeEndTime: state::IsUnset,
+    {
+        self.overtime_end_time(overtime_end_time)
     }
-    pub fn set_overtime_cancellation(mut self, overtime_cancellation: bool) -> Self {
-        self.overtime_cancellation = overtime_cancellation;
-        self
+
+    #[deprecated(note = "use `overtime_cancellation`")]
+    pub fn set_overtime_cancellation(
+        self,
+        overtime_cancellation: bool,
+    ) -> OvertimeBuilder<state::SetOvertimeCancellation<S>>
+    where
+        S::OvertimeCancellation: state::IsUnset,
+    {
+        self.overtime_cancellation(overtime_cancellation)
     }
+
+    #[deprecated(note = "use `employee_profession_code`")]
     pub fn set_employee_profession_code(
-        mut self,
+        self,
         employee_profession_code: impl Into<String>,
-    ) -> Self {
-        self.employee_profession_code = employee_profession_code.into();
-        self
+    ) -> OvertimeBuilder<state::SetEmployeeProfessionCode<S>>
+    where
+        S::EmployeeProfessionCode: state::IsUnset,
+    {
+        self.employee_profession_code(employee_profession_code)
     }
+
+    #[deprecated(note = "use `overtime_justification`")]
     pub fn set_overtime_justification(
-        mut self,
+        self,
         overtime_justification: OvertimeJustificationType,
-    ) -> Self {
-        self.overtime_justification = Some(overtime_justification);
-        self
+    ) -> OvertimeBuilder<state::SetOvertimeJustification<S>>
+    where
+        S::OvertimeJustification: state::IsUnset,
+    {
+        self.overtime_justification(overtime_justification)
     }
-    pub fn set_weekly_workdays_number(mut self, weekly_workdays_number: WeeklyWorkDays) -> Self {
-        self.weekly_workdays_number = weekly_workdays_number;
-        self
+
+    #[deprecated(note = "use `weekly_workdays_number`")]
+    pub fn set_weekly_workdays_number(
+        self,
+        weekly_workdays_number: WeeklyWorkDays,
+    ) -> OvertimeBuilder<state::SetWeeklyWorkdaysNumber<S>>
+    where
+        S::WeeklyWorkdaysNumber: state::IsUnset,
+    {
+        self.weekly_workdays_number(weekly_workdays_number)
     }
-    pub fn set_asee_approval(mut self, asee_approval: Option<impl Into<String>>) -> Self {
-        self.asee_approval = asee_approval.map(|a| a.into());
-        self
+
+    #[deprecated(note = "use `maybe_asee_approval`")]
+    pub fn set_asee_approval(
+        self,
+        asee_approval: Option<impl Into<String>>,
+    ) -> OvertimeBuilder<state::SetAseeApproval<S>>
+    where
+        S::AseeApproval: state::IsUnset,
+    {
+        self.maybe_asee_approval(asee_approval)
     }
 }
 
@@ -118,7 +231,7 @@ mod tests {
     use chrono::{DateTime, NaiveDate, Utc};
 
     #[test]
-    fn test_build_overtime_fails_when_overtime_justification_not_set() {
+    fn test_build_overtime_is_ok() {
         let date_time_start_text = "2014-11-28T12:00:09Z";
         let date_time_end_text = "2014-11-29T12:00:09Z";
 
@@ -126,47 +239,48 @@ mod tests {
         let dt_end = date_time_end_text.parse::<DateTime<Utc>>().unwrap();
 
         let overtime = OvertimeBuilder::builder()
-            .set_employee_tax_identification_number("123456789")
-            .set_employee_social_security_number("12345678901")
-            .set_employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
-            .set_employee_first_name("ΓΕΩΡΓΙΟΣ")
-            .set_overtime_date(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
-            .set_overtime_start_time(dt_start)
-            .set_overtime_end_time(dt_end)
-            .set_overtime_cancellation(false)
-            .set_employee_profession_code("1234")
-            .set_weekly_workdays_number(WeeklyWorkDays::Five)
-            .set_asee_approval(Some("123456"))
+            .employee_tax_identification_number("123456783")
+            .employee_social_security_number("01018012342")
+            .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+            .employee_first_name("ΓΕΩΡΓΙΟΣ")
+            .overtime_date(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
+            .overtime_start_time(dt_start)
+            .overtime_end_time(dt_end)
+            .overtime_cancellation(false)
+            .employee_profession_code("1234")
+            .weekly_workdays_number(WeeklyWorkDays::Five)
+            .asee_approval("123456")
+            .overtime_justification(
+                OvertimeJustificationType::AccidentPreventionOrDamageRestoration,
+            )
             .build();
 
-        assert!(overtime.is_err());
+        assert!(overtime.is_ok());
     }
 
     #[test]
-    fn test_build_overtime_is_ok() {
-        let date_time_start_text = "2014-11-28T12:00:09Z";
-        let date_time_end_text = "2014-11-29T12:00:09Z";
-
-        let dt_start = date_time_start_text.parse::<DateTime<Utc>>().unwrap();
-        let dt_end = date_time_end_text.parse::<DateTime<Utc>>().unwrap();
+    fn test_build_overtime_fails_when_employee_social_security_number_is_invalid() {
+        let dt_start = "2014-11-28T12:00:09Z".parse::<DateTime<Utc>>().unwrap();
+        let dt_end = "2014-11-29T12:00:09Z".parse::<DateTime<Utc>>().unwrap();
 
         let overtime = OvertimeBuilder::builder()
-            .set_employee_tax_identification_number("123456789")
-            .set_employee_social_security_number("12345678901")
-            .set_employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
-            .set_employee_first_name("ΓΕΩΡΓΙΟΣ")
-            .set_overtime_date(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
-            .set_overtime_start_time(dt_start)
-            .set_overtime_end_time(dt_end)
-            .set_overtime_cancellation(false)
-            .set_employee_profession_code("1234")
-            .set_weekly_workdays_number(WeeklyWorkDays::Five)
-            .set_asee_approval(Some("123456"))
-            .set_overtime_justification(
+            .employee_tax_identification_number("123456783")
+            .employee_social_security_number("00000000000")
+            .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+            .employee_first_name("ΓΕΩΡΓΙΟΣ")
+            .overtime_date(NaiveDate::from_ymd_opt(2021, 1, 1).unwrap())
+            .overtime_start_time(dt_start)
+            .overtime_end_time(dt_end)
+            .employee_profession_code("1234")
+            .weekly_workdays_number(WeeklyWorkDays::Five)
+            .overtime_justification(
                 OvertimeJustificationType::AccidentPreventionOrDamageRestoration,
             )
             .build();
 
-        assert!(overtime.is_ok());
+        assert_eq!(
+            overtime.err().unwrap().to_string(),
+            "Invalid employee social security number \"00000000000\": An AMKA starts with a date of birth as DDMMYY"
+        );
     }
 }
@@ -0,0 +1,194 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The number of digits of an AFM
+const AFM_LENGTH: usize = 9;
+
+/// A Greek tax identification number (ΑΦΜ), checked for its length, digits and check digit
+///
+/// The check digit is the last digit: the first eight digits, weighted by descending powers of two from
+/// 2^8 to 2^1, are summed, and the sum modulo 11, modulo 10, must equal it.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Afm(String);
+
+/// Why a string is not a valid [Afm]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum AfmError {
+    /// The AFM does not have 9 characters
+    InvalidLength(usize),
+    /// The AFM contains a character other than a digit
+    NonDigit,
+    /// The AFM consists of zeros only
+    AllZeros,
+    /// The last digit of the AFM does not match its check digit
+    InvalidCheckDigit,
+}
+
+impl Display for AfmError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AfmError::InvalidLength(length) => {
+                write!(
+                    f,
+                    "An AFM has {AFM_LENGTH} digits, found {length} characters"
+                )
+            }
+            AfmError::NonDigit => write!(f, "An AFM contains digits only"),
+            AfmError::AllZeros => write!(f, "An AFM cannot consist of zeros only"),
+            AfmError::InvalidCheckDigit => write!(f, "The check digit of the AFM is wrong"),
+        }
+    }
+}
+
+impl Afm {
+    /// Parses and validates an AFM, ignoring surrounding whitespace
+    ///
+    /// # Arguments
+    /// * - `afm` - The AFM to validate
+    ///
+    /// # Errors
+    /// * - `[AfmError]` - Raised if the AFM has the wrong length, non-digit characters or a wrong check digit
+    pub fn parse(afm: &str) -> Result<Afm, AfmError> {
+        let afm = afm.trim();
+
+        if afm.chars().count() != AFM_LENGTH {
+            return Err(AfmError::InvalidLength(afm.chars().count()));
+        }
+
+        let digits = afm
+            .chars()
+            .map(|character| character.to_digit(10))
+            .collect::<Option<Vec<u32>>>()
+            .ok_or(AfmError::NonDigit)?;
+
+        if digits.iter().all(|digit| *digit == 0) {
+            return Err(AfmError::AllZeros);
+        }
+
+        let weighted_sum: u32 = digits[..AFM_LENGTH - 1]
+            .iter()
+            .enumerate()
+            .map(|(index, digit)| digit << (AFM_LENGTH - 1 - index))
+            .sum();
+
+        if weighted_sum % 11 % 10 != digits[AFM_LENGTH - 1] {
+            return Err(AfmError::InvalidCheckDigit);
+        }
+
+        Ok(Afm(afm.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Parses the AFM set on a builder, naming the field in the error
+///
+/// # Arguments
+/// * - `field` - The description of the field, e.g. `employee tax identification number`
+/// * - `afm` - The value set on the builder
+pub(crate) fn parse_builder_afm(field: &str, afm: &str) -> anyhow::Result<Afm> {
+    Afm::parse(afm).map_err(|error| anyhow::anyhow!("Invalid {field} \"{afm}\": {error}"))
+}
+
+impl FromStr for Afm {
+    type Err = AfmError;
+
+    fn from_str(afm: &str) -> Result<Self, Self::Err> {
+        Afm::parse(afm)
+    }
+}
+
+impl TryFrom<&str> for Afm {
+    type Error = AfmError;
+
+    fn try_from(afm: &str) -> Result<Self, Self::Error> {
+        Afm::parse(afm)
+    }
+}
+
+impl TryFrom<String> for Afm {
+    type Error = AfmError;
+
+    fn try_from(afm: String) -> Result<Self, Self::Error> {
+        Afm::parse(&afm)
+    }
+}
+
+impl From<Afm> for String {
+    fn from(afm: Afm) -> Self {
+        afm.0
+    }
+}
+
+impl AsRef<str> for Afm {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Afm {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Afm {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Display for Afm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Afm {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Afm {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let afm = String::deserialize(deserializer)?;
+
+        Afm::parse(&afm).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_afm() {
+        let afm: Afm = "123456783".parse().unwrap();
+
+        assert_eq!(afm, "123456783");
+        assert_eq!(afm.to_string(), "123456783");
+        assert_eq!(Afm::try_from(" 987654324 ").unwrap().as_str(), "987654324");
+    }
+
+    #[test]
+    fn test_parse_invalid_afm() {
+        assert_eq!(Afm::parse(""), Err(AfmError::InvalidLength(0)));
+        assert_eq!(Afm::parse("12345678"), Err(AfmError::InvalidLength(8)));
+        assert_eq!(Afm::parse("12345678A"), Err(AfmError::NonDigit));
+        assert_eq!(Afm::parse("000000000"), Err(AfmError::AllZeros));
+        assert_eq!(Afm::parse("123456789"), Err(AfmError::InvalidCheckDigit));
+    }
+
+    #[test]
+    fn test_afm_serde_validates() {
+        let afm: Afm = serde_json::from_str(r#""123456783""#).unwrap();
+
+        assert_eq!(serde_json::to_string(&afm).unwrap(), r#""123456783""#);
+        assert!(serde_json::from_str::<Afm>(r#""123456789""#).is_err());
+    }
+}
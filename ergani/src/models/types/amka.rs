@@ -0,0 +1,251 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// The number of digits of an AMKA
+const AMKA_LENGTH: usize = 11;
+
+/// A Greek social security number (ΑΜΚΑ), checked for its length, digits, birth date and check digit
+///
+/// The first six digits are the date of birth of the insured person as `DDMMYY`, and the last digit is a Luhn check
+/// digit over the first ten.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Amka(String);
+
+/// Why a string is not a valid [Amka]
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum AmkaError {
+    /// The AMKA does not have 11 characters
+    InvalidLength(usize),
+    /// The AMKA contains a character other than a digit
+    NonDigit,
+    /// The first six digits of the AMKA are not a date as `DDMMYY`
+    InvalidBirthDate,
+    /// The last digit of the AMKA does not match its Luhn check digit
+    InvalidCheckDigit,
+}
+
+impl Display for AmkaError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AmkaError::InvalidLength(length) => {
+                write!(
+                    f,
+                    "An AMKA has {AMKA_LENGTH} digits, found {length} characters"
+                )
+            }
+            AmkaError::NonDigit => write!(f, "An AMKA contains digits only"),
+            AmkaError::InvalidBirthDate => {
+                write!(f, "An AMKA starts with a date of birth as DDMMYY")
+            }
+            AmkaError::InvalidCheckDigit => write!(f, "The check digit of the AMKA is wrong"),
+        }
+    }
+}
+
+impl Amka {
+    /// Parses and validates an AMKA, ignoring surrounding whitespace
+    ///
+    /// # Arguments
+    /// * - `amka` - The AMKA to validate
+    ///
+    /// # Errors
+    /// * - `[AmkaError]` - Raised if the AMKA has the wrong length, non-digit characters, a wrong date of birth or check digit
+    pub fn parse(amka: &str) -> Result<Amka, AmkaError> {
+        let amka = amka.trim();
+
+        if amka.chars().count() != AMKA_LENGTH {
+            return Err(AmkaError::InvalidLength(amka.chars().count()));
+        }
+
+        let digits = amka
+            .chars()
+            .map(|character| character.to_digit(10))
+            .collect::<Option<Vec<u32>>>()
+            .ok_or(AmkaError::NonDigit)?;
+
+        let amka = Amka(amka.to_string());
+
+        if amka.birth_date_candidates().next().is_none() {
+            return Err(AmkaError::InvalidBirthDate);
+        }
+
+        let luhn_sum: u32 = digits
+            .iter()
+            .rev()
+            .enumerate()
+            .map(|(index, digit)| match index % 2 {
+                0 => *digit,
+                _ if *digit > 4 => digit * 2 - 9,
+                _ => digit * 2,
+            })
+            .sum();
+
+        if luhn_sum % 10 != 0 {
+            return Err(AmkaError::InvalidCheckDigit);
+        }
+
+        Ok(amka)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Returns the date of birth encoded in the AMKA, taking the two-digit year to be in the last hundred years
+    pub fn date_of_birth(&self) -> Option<NaiveDate> {
+        self.date_of_birth_as_of(Utc::now().date_naive())
+    }
+
+    /// Returns the date of birth encoded in the AMKA, taking the two-digit year to be in the hundred years up to a date
+    ///
+    /// # Arguments
+    /// * - `reference_date` - The date the person was already born on, e.g. the date of a submission
+    pub fn date_of_birth_as_of(&self, reference_date: NaiveDate) -> Option<NaiveDate> {
+        self.birth_date_candidates()
+            .filter(|date| *date <= reference_date)
+            .max()
+    }
+
+    /// Returns the dates the `DDMMYY` prefix stands for, in the 20th and the 21st century
+    fn birth_date_candidates(&self) -> impl Iterator<Item = NaiveDate> + '_ {
+        let part = |range: std::ops::Range<usize>| self.0[range].parse::<u32>().ok();
+        let (day, month, year) = (part(0..2), part(2..4), part(4..6));
+
+        [1900, 2000].into_iter().filter_map(move |century| {
+            NaiveDate::from_ymd_opt(century + year? as i32, month?, day?)
+        })
+    }
+}
+
+/// Parses the AMKA set on a builder, naming the field in the error
+///
+/// # Arguments
+/// * - `field` - The description of the field, e.g. `employee social security number`
+/// * - `amka` - The value set on the builder
+pub(crate) fn parse_builder_amka(field: &str, amka: &str) -> anyhow::Result<Amka> {
+    Amka::parse(amka).map_err(|error| anyhow::anyhow!("Invalid {field} \"{amka}\": {error}"))
+}
+
+impl FromStr for Amka {
+    type Err = AmkaError;
+
+    fn from_str(amka: &str) -> Result<Self, Self::Err> {
+        Amka::parse(amka)
+    }
+}
+
+impl TryFrom<&str> for Amka {
+    type Error = AmkaError;
+
+    fn try_from(amka: &str) -> Result<Self, Self::Error> {
+        Amka::parse(amka)
+    }
+}
+
+impl TryFrom<String> for Amka {
+    type Error = AmkaError;
+
+    fn try_from(amka: String) -> Result<Self, Self::Error> {
+        Amka::parse(&amka)
+    }
+}
+
+impl From<Amka> for String {
+    fn from(amka: Amka) -> Self {
+        amka.0
+    }
+}
+
+impl AsRef<str> for Amka {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Amka {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Amka {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl Display for Amka {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Serialize for Amka {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amka {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let amka = String::deserialize(deserializer)?;
+
+        Amka::parse(&amka).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_valid_amka() {
+        let amka: Amka = "01018012342".parse().unwrap();
+
+        assert_eq!(amka, "01018012342");
+        assert_eq!(amka.to_string(), "01018012342");
+        assert_eq!(
+            Amka::try_from(" 15039200124 ").unwrap().as_str(),
+            "15039200124"
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_amka() {
+        assert_eq!(Amka::parse(""), Err(AmkaError::InvalidLength(0)));
+        assert_eq!(Amka::parse("0101801234"), Err(AmkaError::InvalidLength(10)));
+        assert_eq!(Amka::parse("0101801234A"), Err(AmkaError::NonDigit));
+        assert_eq!(Amka::parse("00000000000"), Err(AmkaError::InvalidBirthDate));
+        assert_eq!(Amka::parse("31028012342"), Err(AmkaError::InvalidBirthDate));
+        assert_eq!(
+            Amka::parse("01018012343"),
+            Err(AmkaError::InvalidCheckDigit)
+        );
+        assert!(serde_json::from_str::<Amka>(r#""01018012343""#).is_err());
+    }
+
+    #[test]
+    fn test_amka_date_of_birth() {
+        let amka: Amka = "01018012342".parse().unwrap();
+        let reference_date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+        assert_eq!(
+            amka.date_of_birth_as_of(reference_date),
+            NaiveDate::from_ymd_opt(1980, 1, 1)
+        );
+
+        let amka: Amka = "15031500000".parse().unwrap();
+
+        assert_eq!(
+            amka.date_of_birth_as_of(reference_date),
+            NaiveDate::from_ymd_opt(2015, 3, 15)
+        );
+        assert_eq!(
+            amka.date_of_birth_as_of(NaiveDate::from_ymd_opt(2010, 1, 1).unwrap()),
+            NaiveDate::from_ymd_opt(1915, 3, 15)
+        );
+    }
+}
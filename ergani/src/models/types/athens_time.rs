@@ -0,0 +1,216 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::{DateTime, LocalResult, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+/// The time zone of the clocks in Greece, which the Ergani API reads every time in
+pub const ATHENS: Tz = chrono_tz::Europe::Athens;
+
+/// Why a date and time cannot be placed on the clocks in Greece
+#[derive(thiserror::Error, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AthensTimeError {
+    /// The clocks go forward over the date and time, e.g. 03:30 on the last Sunday of March
+    Nonexistent(NaiveDateTime),
+    /// The clocks go back over the date and time, so it happens twice, e.g. 03:30 on the last Sunday of October
+    Ambiguous(NaiveDateTime),
+}
+
+impl Display for AthensTimeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AthensTimeError::Nonexistent(datetime) => write!(
+                f,
+                "{datetime} does not exist in {ATHENS}, the clocks go forward over it"
+            ),
+            AthensTimeError::Ambiguous(datetime) => write!(
+                f,
+                "{datetime} happens twice in {ATHENS}, pass it with its offset to pick one"
+            ),
+        }
+    }
+}
+
+/// Checks that a date and time on the clocks in Greece happens exactly once
+fn resolve_local(datetime: NaiveDateTime) -> Result<NaiveDateTime, AthensTimeError> {
+    match ATHENS.from_local_datetime(&datetime) {
+        LocalResult::Single(_) => Ok(datetime),
+        LocalResult::None => Err(AthensTimeError::Nonexistent(datetime)),
+        LocalResult::Ambiguous(_, _) => Err(AthensTimeError::Ambiguous(datetime)),
+    }
+}
+
+/// A date and time sent to the Ergani API, which reads it on the clocks in Greece
+///
+/// Any [DateTime] converts into an `Instant`, whatever its time zone, and a [NaiveDateTime] into a `Local`, taken as
+/// the date and time in Greece. Both are converted to Europe/Athens time when serialized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AthensDateTime {
+    /// An instant, shown as the date and time in Greece at that instant
+    Instant(DateTime<Utc>),
+    /// A date and time on the clocks in Greece
+    Local(NaiveDateTime),
+}
+
+impl AthensDateTime {
+    /// Returns the date and time on the clocks in Greece
+    ///
+    /// # Errors
+    /// * - `[AthensTimeError]` - Raised if a `Local` date and time is skipped or repeated by a daylight saving change
+    pub fn local(&self) -> Result<NaiveDateTime, AthensTimeError> {
+        match self {
+            AthensDateTime::Instant(instant) => Ok(instant.with_timezone(&ATHENS).naive_local()),
+            AthensDateTime::Local(datetime) => resolve_local(*datetime),
+        }
+    }
+}
+
+impl<Zone: TimeZone> From<DateTime<Zone>> for AthensDateTime {
+    fn from(instant: DateTime<Zone>) -> Self {
+        AthensDateTime::Instant(instant.with_timezone(&Utc))
+    }
+}
+
+impl From<NaiveDateTime> for AthensDateTime {
+    fn from(datetime: NaiveDateTime) -> Self {
+        AthensDateTime::Local(datetime)
+    }
+}
+
+impl Display for AthensDateTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AthensDateTime::Instant(instant) => write!(f, "{instant}"),
+            AthensDateTime::Local(datetime) => write!(f, "{datetime} {ATHENS}"),
+        }
+    }
+}
+
+/// A time of day sent to the Ergani API, which reads it on the clocks in Greece
+///
+/// Any [DateTime] converts into an `Instant`, whatever its time zone, a [NaiveDateTime] into a `LocalDateTime` and a
+/// [NaiveTime] into a `LocalTime`, both taken as the time in Greece. They are converted to Europe/Athens time when
+/// serialized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AthensTime {
+    /// An instant, shown as the time in Greece at that instant
+    Instant(DateTime<Utc>),
+    /// A date and time on the clocks in Greece
+    LocalDateTime(NaiveDateTime),
+    /// A time on the clocks in Greece, on no particular date
+    LocalTime(NaiveTime),
+}
+
+impl AthensTime {
+    /// Returns the time on the clocks in Greece
+    ///
+    /// # Errors
+    /// * - `[AthensTimeError]` - Raised if a `LocalDateTime` is skipped or repeated by a daylight saving change
+    pub fn local(&self) -> Result<NaiveTime, AthensTimeError> {
+        match self {
+            AthensTime::Instant(instant) => Ok(instant.with_timezone(&ATHENS).time()),
+            AthensTime::LocalDateTime(datetime) => Ok(resolve_local(*datetime)?.time()),
+            AthensTime::LocalTime(time) => Ok(*time),
+        }
+    }
+
+    /// Returns the date and time on the clocks in Greece, or `None` for a `LocalTime`, which has no date
+    ///
+    /// # Errors
+    /// * - `[AthensTimeError]` - Raised if a `LocalDateTime` is skipped or repeated by a daylight saving change
+    pub fn local_datetime(&self) -> Result<Option<NaiveDateTime>, AthensTimeError> {
+        match self {
+            AthensTime::Instant(instant) => Ok(Some(instant.with_timezone(&ATHENS).naive_local())),
+            AthensTime::LocalDateTime(datetime) => resolve_local(*datetime).map(Some),
+            AthensTime::LocalTime(_) => Ok(None),
+        }
+    }
+}
+
+impl<Zone: TimeZone> From<DateTime<Zone>> for AthensTime {
+    fn from(instant: DateTime<Zone>) -> Self {
+        AthensTime::Instant(instant.with_timezone(&Utc))
+    }
+}
+
+impl From<NaiveDateTime> for AthensTime {
+    fn from(datetime: NaiveDateTime) -> Self {
+        AthensTime::LocalDateTime(datetime)
+    }
+}
+
+impl From<NaiveTime> for AthensTime {
+    fn from(time: NaiveTime) -> Self {
+        AthensTime::LocalTime(time)
+    }
+}
+
+impl Display for AthensTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AthensTime::Instant(instant) => write!(f, "{instant}"),
+            AthensTime::LocalDateTime(datetime) => write!(f, "{datetime} {ATHENS}"),
+            AthensTime::LocalTime(time) => write!(f, "{time} {ATHENS}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, NaiveDate};
+
+    fn naive(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_instants_are_shown_in_athens_time() {
+        let winter = AthensDateTime::from(Utc.with_ymd_and_hms(2024, 1, 15, 7, 0, 0).unwrap());
+        let summer = AthensTime::from(Utc.with_ymd_and_hms(2024, 7, 15, 6, 0, 0).unwrap());
+        let new_york = FixedOffset::west_opt(4 * 3600).unwrap();
+        let offset = AthensTime::from(new_york.with_ymd_and_hms(2024, 7, 15, 2, 0, 0).unwrap());
+
+        assert_eq!(winter.local(), Ok(naive(2024, 1, 15, 9, 0)));
+        assert_eq!(
+            summer.local(),
+            Ok(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+        );
+        assert_eq!(offset.local(), summer.local());
+    }
+
+    #[test]
+    fn test_local_times_are_kept() {
+        let datetime = AthensDateTime::from(naive(2024, 3, 20, 9, 0));
+        let time = AthensTime::from(NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+
+        assert_eq!(datetime.local(), Ok(naive(2024, 3, 20, 9, 0)));
+        assert_eq!(time.local(), Ok(NaiveTime::from_hms_opt(9, 0, 0).unwrap()));
+        assert_eq!(time.local_datetime(), Ok(None));
+    }
+
+    #[test]
+    fn test_local_times_around_daylight_saving_changes_are_rejected() {
+        let skipped = naive(2024, 3, 31, 3, 30);
+        let repeated = naive(2024, 10, 27, 3, 30);
+
+        assert_eq!(
+            AthensDateTime::from(skipped).local(),
+            Err(AthensTimeError::Nonexistent(skipped))
+        );
+        assert_eq!(
+            AthensTime::from(repeated).local(),
+            Err(AthensTimeError::Ambiguous(repeated))
+        );
+        assert_eq!(
+            AthensTimeError::Nonexistent(skipped).to_string(),
+            "2024-03-31 03:30:00 does not exist in Europe/Athens, the clocks go forward over it"
+        );
+        assert_eq!(
+            AthensTime::from(Utc.with_ymd_and_hms(2024, 10, 27, 0, 30, 0).unwrap()).local(),
+            Ok(NaiveTime::from_hms_opt(3, 30, 0).unwrap())
+        );
+    }
+}
@@ -1,3 +1,6 @@
+pub mod afm;
+pub mod amka;
+pub mod athens_time;
 pub mod late_declaration_justification_type;
 pub mod overtime_justification_type;
 pub mod schedule_work_type;
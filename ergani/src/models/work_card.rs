@@ -1,8 +1,11 @@
 use crate::internal::utils::format_datetime;
+use crate::models::types::afm::Afm;
+use crate::models::types::athens_time::AthensDateTime;
 use crate::models::types::late_declaration_justification_type::LateDeclarationJustificationType;
 use crate::models::types::work_card_movement_type::WorkCardMovementType;
-use chrono::{DateTime, NaiveDate, Utc};
-use serde::ser::{Serialize, SerializeStruct, Serializer};
+use crate::validation::{check_athens_time, check_not_blank, Validate, ValidationIssue};
+use chrono::NaiveDate;
+use serde::ser::{Error, Serialize, SerializeStruct, Serializer};
 
 /// Represents a work card entry for an employee
 /// * - `employee_tax_identification_number` - The employee's tax identification number
@@ -10,16 +13,16 @@ use serde::ser::{Serialize, SerializeStruct, Serializer};
 /// * - `employee_first_name` - The first name of the employee
 /// * - `work_card_movement_type` - The type of work card movement
 /// * - `work_card_submission_date` - The date the work card was submitted
-/// * - `work_card_movement_datetime` - The exact date and time of the work card movement
+/// * - `work_card_movement_datetime` - The exact date and time of the work card movement, sent as the date and time in Greece
 /// * - `late_declaration_justification` - The justification for the late declaration of the work card movement
 #[derive(Clone)]
 pub struct WorkCard {
-    pub employee_tax_identification_number: String,
+    pub employee_tax_identification_number: Afm,
     pub employee_last_name: String,
     pub employee_first_name: String,
     pub work_card_movement_type: WorkCardMovementType,
     pub work_card_submission_date: NaiveDate,
-    pub work_card_movement_datetime: DateTime<Utc>,
+    pub work_card_movement_datetime: AthensDateTime,
     pub late_declaration_justification: Option<LateDeclarationJustificationType>,
 }
 
@@ -40,7 +43,8 @@ impl Serialize for WorkCard {
 
         let work_card_movement_type = self.work_card_movement_type.value();
 
-        let work_card_movement_datetime = format_datetime(Some(&self.work_card_movement_datetime));
+        let work_card_movement_datetime =
+            format_datetime(Some(&self.work_card_movement_datetime)).map_err(S::Error::custom)?;
 
         let mut work_card = serializer.serialize_struct("WorkCard", 5)?;
         work_card.serialize_field("f_afm", &self.employee_tax_identification_number)?;
@@ -54,10 +58,28 @@ impl Serialize for WorkCard {
     }
 }
 
+impl Validate for WorkCard {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        check_not_blank(path, "employee_last_name", &self.employee_last_name, issues);
+        check_not_blank(
+            path,
+            "employee_first_name",
+            &self.employee_first_name,
+            issues,
+        );
+        check_athens_time(
+            path,
+            "work_card_movement_datetime",
+            self.work_card_movement_datetime.local(),
+            issues,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::NaiveDate;
+    use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
 
     #[test]
     fn test_serialize_work_card_with_late_declaration_justification() {
@@ -65,37 +87,62 @@ mod tests {
         let dt = date_time.parse::<DateTime<Utc>>().unwrap();
 
         let work_card = WorkCard {
-            employee_tax_identification_number: "123456789".to_string(),
+            employee_tax_identification_number: "123456783".parse().unwrap(),
             employee_last_name: "ΠΑΠΑΔΟΠΟΥΛΟΣ".to_string(),
             employee_first_name: "ΓΕΩΡΓΙΟΣ".to_string(),
             work_card_movement_type: WorkCardMovementType::Arrival,
             work_card_submission_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
-            work_card_movement_datetime: dt,
+            work_card_movement_datetime: dt.into(),
             late_declaration_justification: Some(LateDeclarationJustificationType::PowerOutage),
         };
 
         let serialized_work_card = serde_json::to_string(&work_card).unwrap();
-        let expected_work_card = r#"{"f_afm":"123456789","f_eponymo":"ΠΑΠΑΔΟΠΟΥΛΟΣ","f_onoma":"ΓΕΩΡΓΙΟΣ","f_type":"0","f_reference_date":"2021-01-01","f_date":"2014-11-28T12:00:00","f_aitiologia":"001"}"#;
+        let expected_work_card = r#"{"f_afm":"123456783","f_eponymo":"ΠΑΠΑΔΟΠΟΥΛΟΣ","f_onoma":"ΓΕΩΡΓΙΟΣ","f_type":"0","f_reference_date":"2021-01-01","f_date":"2014-11-28T14:00:00","f_aitiologia":"001"}"#;
         assert_eq!(serialized_work_card, expected_work_card);
     }
 
     #[test]
     fn test_serialize_work_card_without_late_declaration_justification() {
-        let date_time = "2014-11-28T12:00:00Z";
-        let dt = date_time.parse::<DateTime<Utc>>().unwrap();
+        let date_time = "2014-11-28T12:00:00";
+        let dt = date_time.parse::<NaiveDateTime>().unwrap();
 
         let work_card = WorkCard {
-            employee_tax_identification_number: "123456789".to_string(),
+            employee_tax_identification_number: "123456783".parse().unwrap(),
             employee_last_name: "ΠΑΠΑΔΟΠΟΥΛΟΣ".to_string(),
             employee_first_name: "ΓΕΩΡΓΙΟΣ".to_string(),
             work_card_movement_type: WorkCardMovementType::Arrival,
             work_card_submission_date: NaiveDate::from_ymd_opt(2021, 1, 1).unwrap(),
-            work_card_movement_datetime: dt,
+            work_card_movement_datetime: dt.into(),
             late_declaration_justification: None,
         };
 
         let serialized_work_card = serde_json::to_string(&work_card).unwrap();
-        let expected_work_card = r#"{"f_afm":"123456789","f_eponymo":"ΠΑΠΑΔΟΠΟΥΛΟΣ","f_onoma":"ΓΕΩΡΓΙΟΣ","f_type":"0","f_reference_date":"2021-01-01","f_date":"2014-11-28T12:00:00","f_aitiologia":""}"#;
+        let expected_work_card = r#"{"f_afm":"123456783","f_eponymo":"ΠΑΠΑΔΟΠΟΥΛΟΣ","f_onoma":"ΓΕΩΡΓΙΟΣ","f_type":"0","f_reference_date":"2021-01-01","f_date":"2014-11-28T12:00:00","f_aitiologia":""}"#;
         assert_eq!(serialized_work_card, expected_work_card);
     }
+
+    #[test]
+    fn test_work_card_movement_skipped_by_daylight_saving_is_rejected() {
+        let work_card = WorkCard {
+            employee_tax_identification_number: "123456783".parse().unwrap(),
+            employee_last_name: "ΠΑΠΑΔΟΠΟΥΛΟΣ".to_string(),
+            employee_first_name: "ΓΕΩΡΓΙΟΣ".to_string(),
+            work_card_movement_type: WorkCardMovementType::Arrival,
+            work_card_submission_date: NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+            work_card_movement_datetime: "2024-03-31T03:30:00"
+                .parse::<NaiveDateTime>()
+                .unwrap()
+                .into(),
+            late_declaration_justification: None,
+        };
+
+        assert_eq!(
+            work_card.issues(),
+            [ValidationIssue::new(
+                "work_card_movement_datetime",
+                "2024-03-31 03:30:00 does not exist in Europe/Athens, the clocks go forward over it"
+            )]
+        );
+        assert!(serde_json::to_string(&work_card).is_err());
+    }
 }
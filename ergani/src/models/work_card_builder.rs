@@ -1,85 +1,150 @@
 #![allow(dead_code)]
 
+use crate::models::types::afm::parse_builder_afm;
+use crate::models::types::athens_time::AthensDateTime;
 use crate::models::types::late_declaration_justification_type::LateDeclarationJustificationType;
 use crate::models::types::work_card_movement_type::WorkCardMovementType;
 use crate::models::work_card::WorkCard;
-use anyhow::{bail, Result};
-use chrono::{DateTime, NaiveDate, Utc};
-
-#[derive(Default)]
-pub struct WorkCardBuilder {
-    pub(crate) employee_tax_identification_number: String,
-    pub(crate) employee_last_name: String,
-    pub(crate) employee_first_name: String,
-    pub(crate) work_card_movement_type: Option<WorkCardMovementType>,
-    pub(crate) work_card_submission_date: NaiveDate,
-    pub(crate) work_card_movement_datetime: DateTime<Utc>,
-    pub(crate) late_declaration_justification: Option<LateDeclarationJustificationType>,
-}
-
-impl WorkCardBuilder {
-    pub fn builder() -> WorkCardBuilder {
-        WorkCardBuilder::default()
-    }
+use anyhow::Result;
+use bon::bon;
+use chrono::NaiveDate;
 
-    pub fn build(self) -> Result<WorkCard> {
-        let work_card_movement_type = match self.work_card_movement_type {
-            Some(wcmt) => wcmt,
-            None => bail!("Work card movement type is required"),
-        };
+#[bon]
+impl WorkCard {
+    /// Starts building a `WorkCard`
+    ///
+    /// The movement date and time takes any [DateTime](chrono::DateTime), or a [NaiveDateTime](chrono::NaiveDateTime)
+    /// on the clocks in Greece.
+    ///
+    /// Every field but `late_declaration_justification` must be set, or `build()` does not compile:
+    ///
+    /// ```compile_fail
+    /// use chrono::{NaiveDate, Utc};
+    /// use ergani::models::types::work_card_movement_type::WorkCardMovementType;
+    /// use ergani::models::work_card_builder::WorkCardBuilder;
+    ///
+    /// let work_card = WorkCardBuilder::builder()
+    ///     .employee_tax_identification_number("123456783")
+    ///     .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+    ///     .employee_first_name("ΓΕΩΡΓΙΟΣ")
+    ///     .work_card_movement_type(WorkCardMovementType::Arrival)
+    ///     .work_card_submission_date(NaiveDate::from_ymd_opt(2024, 3, 20).unwrap())
+    ///     // `work_card_movement_datetime` is missing
+    ///     .build();
+    /// ```
+    ///
+    /// # Errors
+    /// * - `build()` fails if `employee_tax_identification_number` is not a valid AFM
+    #[builder(state_mod = state)]
+    pub fn new(
+        #[builder(into)] employee_tax_identification_number: String,
+        #[builder(into)] employee_last_name: String,
+        #[builder(into)] employee_first_name: String,
+        work_card_movement_type: WorkCardMovementType,
+        work_card_submission_date: NaiveDate,
+        #[builder(into)] work_card_movement_datetime: AthensDateTime,
+        late_declaration_justification: Option<LateDeclarationJustificationType>,
+    ) -> Result<WorkCard> {
+        let employee_tax_identification_number = parse_builder_afm(
+            "employee tax identification number",
+            &employee_tax_identification_number,
+        )?;
 
         Ok(WorkCard {
-            employee_tax_identification_number: self.employee_tax_identification_number,
-            employee_last_name: self.employee_last_name,
-            employee_first_name: self.employee_first_name,
-            work_card_submission_date: self.work_card_submission_date,
-            work_card_movement_datetime: self.work_card_movement_datetime,
-            late_declaration_justification: self.late_declaration_justification,
+            employee_tax_identification_number,
+            employee_last_name,
+            employee_first_name,
             work_card_movement_type,
+            work_card_submission_date,
+            work_card_movement_datetime,
+            late_declaration_justification,
         })
     }
+}
+
+impl WorkCardBuilder {
+    /// Starts building a `WorkCard`
+    pub fn builder() -> WorkCardBuilder {
+        WorkCard::builder()
+    }
+}
 
+impl<S: state::State> WorkCardBuilder<S> {
+    #[deprecated(note = "use `employee_tax_identification_number`")]
     pub fn set_employee_tax_identification_number(
-        mut self,
+        self,
         employee_tax_identification_number: impl Into<String>,
-    ) -> Self {
-        self.employee_tax_identification_number = employee_tax_identification_number.into();
-        self
+    ) -> WorkCardBuilder<state::SetEmployeeTaxIdentificationNumber<S>>
+    where
+        S::EmployeeTaxIdentificationNumber: state::IsUnset,
+    {
+        self.employee_tax_identification_number(employee_tax_identification_number)
     }
 
-    pub fn set_employee_last_name(mut self, employee_last_name: impl Into<String>) -> Self {
-        self.employee_last_name = employee_last_name.into();
-        self
+    #[deprecated(note = "use `employee_last_name`")]
+    pub fn set_employee_last_name(
+        self,
+        employee_last_name: impl Into<String>,
+    ) -> WorkCardBuilder<state::SetEmployeeLastName<S>>
+    where
+        S::EmployeeLastName: state::IsUnset,
+    {
+        self.employee_last_name(employee_last_name)
     }
-    pub fn set_employee_first_name(mut self, employee_first_name: impl Into<String>) -> Self {
-        self.employee_first_name = employee_first_name.into();
-        self
+
+    #[deprecated(note = "use `employee_first_name`")]
+    pub fn set_employee_first_name(
+        self,
+        employee_first_name: impl Into<String>,
+    ) -> WorkCardBuilder<state::SetEmployeeFirstName<S>>
+    where
+        S::EmployeeFirstName: state::IsUnset,
+    {
+        self.employee_first_name(employee_first_name)
     }
 
+    #[deprecated(note = "use `work_card_movement_type`")]
     pub fn set_work_card_movement_type(
-        mut self,
+        self,
         work_card_movement_type: WorkCardMovementType,
-    ) -> Self {
-        self.work_card_movement_type = Some(work_card_movement_type);
-        self
+    ) -> WorkCardBuilder<state::SetWorkCardMovementType<S>>
+    where
+        S::WorkCardMovementType: state::IsUnset,
+    {
+        self.work_card_movement_type(work_card_movement_type)
     }
-    pub fn set_work_card_submission_date(mut self, work_card_submission_date: NaiveDate) -> Self {
-        self.work_card_submission_date = work_card_submission_date;
-        self
+
+    #[deprecated(note = "use `work_card_submission_date`")]
+    pub fn set_work_card_submission_date(
+        self,
+        work_card_submission_date: NaiveDate,
+    ) -> WorkCardBuilder<state::SetWorkCardSubmissionDate<S>>
+    where
+        S::WorkCardSubmissionDate: state::IsUnset,
+    {
+        self.work_card_submission_date(work_card_submission_date)
     }
+
+    #[deprecated(note = "use `work_card_movement_datetime`")]
     pub fn set_work_card_movement_datetime(
-        mut self,
-        work_card_movement_datetime: DateTime<Utc>,
-    ) -> Self {
-        self.work_card_movement_datetime = work_card_movement_datetime;
-        self
+        self,
+        work_card_movement_datetime: impl Into<AthensDateTime>,
+    ) -> WorkCardBuilder<state::SetWorkCardMovementDatetime<S>>
+    where
+        S::WorkCardMovementDatetime: state::IsUnset,
+    {
+        self.work_card_movement_datetime(work_card_movement_datetime)
     }
+
+    #[deprecated(note = "use `maybe_late_declaration_justification`")]
     pub fn set_late_declaration_justification(
-        mut self,
+        self,
         late_declaration_justification: Option<LateDeclarationJustificationType>,
-    ) -> Self {
-        self.late_declaration_justification = late_declaration_justification;
-        self
+    ) -> WorkCardBuilder<state::SetLateDeclarationJustification<S>>
+    where
+        S::LateDeclarationJustification: state::IsUnset,
+    {
+        self.maybe_late_declaration_justification(late_declaration_justification)
     }
 }
 
@@ -87,23 +152,39 @@ impl WorkCardBuilder {
 mod tests {
     use super::*;
     use crate::models::types::late_declaration_justification_type::LateDeclarationJustificationType;
-    use chrono::NaiveDate;
+    use chrono::{DateTime, NaiveDate, Utc};
 
     #[test]
-    fn test_build_work_card_fails_when_work_card_movement_type_not_set() {
-        let date_time = "2014-11-28T12:00:00Z";
-        let dt = date_time.parse::<DateTime<Utc>>().unwrap();
+    #[allow(deprecated)]
+    fn test_deprecated_setters_build_the_same_work_card() {
+        let dt = "2014-11-28T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let submission_date = NaiveDate::from_ymd_opt(2014, 11, 28).unwrap();
 
         let work_card = WorkCardBuilder::builder()
-            .set_employee_tax_identification_number("123456789")
+            .set_employee_tax_identification_number("123456783")
             .set_employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
             .set_employee_first_name("ΓΕΩΡΓΙΟΣ")
-            .set_work_card_submission_date(NaiveDate::from_ymd_opt(2014, 11, 28).unwrap())
+            .set_work_card_movement_type(WorkCardMovementType::Arrival)
+            .set_work_card_submission_date(submission_date)
             .set_work_card_movement_datetime(dt)
             .set_late_declaration_justification(Some(LateDeclarationJustificationType::PowerOutage))
-            .build();
+            .build()
+            .unwrap();
+        let expected = WorkCard::builder()
+            .employee_tax_identification_number("123456783")
+            .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+            .employee_first_name("ΓΕΩΡΓΙΟΣ")
+            .work_card_movement_type(WorkCardMovementType::Arrival)
+            .work_card_submission_date(submission_date)
+            .work_card_movement_datetime(dt)
+            .late_declaration_justification(LateDeclarationJustificationType::PowerOutage)
+            .build()
+            .unwrap();
 
-        assert!(work_card.is_err());
+        assert_eq!(
+            serde_json::to_value(&work_card).unwrap(),
+            serde_json::to_value(&expected).unwrap()
+        );
     }
 
     #[test]
@@ -112,15 +193,35 @@ mod tests {
         let dt = date_time.parse::<DateTime<Utc>>().unwrap();
 
         let work_card = WorkCardBuilder::builder()
-            .set_employee_tax_identification_number("123456789")
-            .set_employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
-            .set_employee_first_name("ΓΕΩΡΓΙΟΣ")
-            .set_work_card_submission_date(NaiveDate::from_ymd_opt(2014, 11, 28).unwrap())
-            .set_work_card_movement_datetime(dt)
-            .set_late_declaration_justification(Some(LateDeclarationJustificationType::PowerOutage))
-            .set_work_card_movement_type(WorkCardMovementType::Arrival)
+            .employee_tax_identification_number("123456783")
+            .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+            .employee_first_name("ΓΕΩΡΓΙΟΣ")
+            .work_card_submission_date(NaiveDate::from_ymd_opt(2014, 11, 28).unwrap())
+            .work_card_movement_datetime(dt)
+            .late_declaration_justification(LateDeclarationJustificationType::PowerOutage)
+            .work_card_movement_type(WorkCardMovementType::Arrival)
             .build();
 
         assert!(work_card.is_ok());
     }
+
+    #[test]
+    fn test_build_work_card_fails_when_employee_tax_identification_number_is_invalid() {
+        let date_time = "2014-11-28T12:00:00Z";
+        let dt = date_time.parse::<DateTime<Utc>>().unwrap();
+
+        let work_card = WorkCardBuilder::builder()
+            .employee_tax_identification_number("123456789")
+            .employee_last_name("ΠΑΠΑΔΟΠΟΥΛΟΣ")
+            .employee_first_name("ΓΕΩΡΓΙΟΣ")
+            .work_card_submission_date(NaiveDate::from_ymd_opt(2014, 11, 28).unwrap())
+            .work_card_movement_datetime(dt)
+            .work_card_movement_type(WorkCardMovementType::Arrival)
+            .build();
+
+        assert_eq!(
+            work_card.err().unwrap().to_string(),
+            "Invalid employee tax identification number \"123456789\": The check digit of the AFM is wrong"
+        );
+    }
 }
@@ -1,17 +1,18 @@
 use crate::internal::utils::format_time;
+use crate::models::types::athens_time::AthensTime;
 use crate::models::types::schedule_work_type::ScheduleWorkType;
-use chrono::{DateTime, Utc};
-use serde::ser::{Serialize, SerializeStruct, Serializer};
+use crate::validation::{check_time_period, Validate, ValidationIssue};
+use serde::ser::{Error, Serialize, SerializeStruct, Serializer};
 
 /// Represents details of an employee's workday
 /// * - `work_type` - The type of an employee's work schedule
-/// * - `start_time` - The start time of the workday
-/// * - `end_time` - The end time of the workday
+/// * - `start_time` - The start time of the workday, sent as the time in Greece
+/// * - `end_time` - The end time of the workday, sent as the time in Greece
 #[derive(Clone)]
 pub struct WorkDayDetails {
     pub work_type: ScheduleWorkType,
-    pub start_time: DateTime<Utc>,
-    pub end_time: DateTime<Utc>,
+    pub start_time: AthensTime,
+    pub end_time: AthensTime,
 }
 
 impl Serialize for WorkDayDetails {
@@ -19,8 +20,8 @@ impl Serialize for WorkDayDetails {
     where
         S: Serializer,
     {
-        let formatted_start_time = format_time(&self.start_time);
-        let formatted_end_time = format_time(&self.end_time);
+        let formatted_start_time = format_time(&self.start_time).map_err(S::Error::custom)?;
+        let formatted_end_time = format_time(&self.end_time).map_err(S::Error::custom)?;
 
         let mut work_day_details = serializer.serialize_struct("WorkDayDetails", 3)?;
         work_day_details.serialize_field("f_type", &self.work_type.value())?;
@@ -30,11 +31,23 @@ impl Serialize for WorkDayDetails {
     }
 }
 
+impl Validate for WorkDayDetails {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        check_time_period(
+            path,
+            ("start_time", &self.start_time),
+            ("end_time", &self.end_time),
+            issues,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::internal::tests::load_fixture_as_text;
     use crate::models::types::schedule_work_type::ScheduleWorkType;
+    use chrono::{DateTime, Utc};
 
     #[test]
     fn test_serialize_work_day_details() {
@@ -42,8 +55,8 @@ mod tests {
         let end_time = "2014-11-28T16:00:00Z";
         let work_day_details = WorkDayDetails {
             work_type: ScheduleWorkType::WorkFromHome,
-            start_time: start_time.parse::<DateTime<Utc>>().unwrap(),
-            end_time: end_time.parse::<DateTime<Utc>>().unwrap(),
+            start_time: start_time.parse::<DateTime<Utc>>().unwrap().into(),
+            end_time: end_time.parse::<DateTime<Utc>>().unwrap().into(),
         };
 
         let serialized_work_day_details = serde_json::to_string(&work_day_details).unwrap();
@@ -1,81 +1,118 @@
 #![allow(dead_code)]
 
+use crate::models::types::athens_time::AthensTime;
 use crate::models::types::schedule_work_type::ScheduleWorkType;
 use crate::models::work_day_details::WorkDayDetails;
-use anyhow::{bail, Result};
-use chrono::{DateTime, Utc};
+use bon::bon;
 
-#[derive(Default)]
-pub struct WorkDayDetailsBuilder {
-    pub(crate) work_type: Option<ScheduleWorkType>,
-    pub(crate) start_time: DateTime<Utc>,
-    pub(crate) end_time: DateTime<Utc>,
+#[bon]
+impl WorkDayDetails {
+    /// Starts building the `WorkDayDetails` of a schedule
+    ///
+    /// The times take any [DateTime](chrono::DateTime), or a [NaiveTime](chrono::NaiveTime) or
+    /// [NaiveDateTime](chrono::NaiveDateTime) on the clocks in Greece.
+    ///
+    /// Every field must be set, or `build()` does not compile:
+    ///
+    /// ```compile_fail
+    /// use chrono::{TimeZone, Utc};
+    /// use ergani::models::work_day_details_builder::WorkDayDetailsBuilder;
+    ///
+    /// let work_day_details = WorkDayDetailsBuilder::builder()
+    ///     .start_time(Utc.with_ymd_and_hms(2024, 3, 20, 7, 0, 0).unwrap())
+    ///     .end_time(Utc.with_ymd_and_hms(2024, 3, 20, 15, 0, 0).unwrap())
+    ///     // `work_type` is missing
+    ///     .build();
+    /// ```
+    #[builder(state_mod = state)]
+    pub fn new(
+        work_type: ScheduleWorkType,
+        #[builder(into)] start_time: AthensTime,
+        #[builder(into)] end_time: AthensTime,
+    ) -> WorkDayDetails {
+        WorkDayDetails {
+            work_type,
+            start_time,
+            end_time,
+        }
+    }
 }
 
 impl WorkDayDetailsBuilder {
+    /// Starts building the `WorkDayDetails` of a schedule
     pub fn builder() -> WorkDayDetailsBuilder {
-        WorkDayDetailsBuilder::default()
-    }
-
-    pub fn build(self) -> Result<WorkDayDetails> {
-        let work_type = match self.work_type {
-            Some(wt) => wt,
-            None => bail!("Work type is required"),
-        };
-
-        Ok(WorkDayDetails {
-            start_time: self.start_time,
-            end_time: self.end_time,
-            work_type,
-        })
+        WorkDayDetails::builder()
     }
+}
 
-    pub fn set_work_type(mut self, work_type: ScheduleWorkType) -> Self {
-        self.work_type = Some(work_type);
-        self
+impl<S: state::State> WorkDayDetailsBuilder<S> {
+    #[deprecated(note = "use `work_type`")]
+    pub fn set_work_type(
+        self,
+        work_type: ScheduleWorkType,
+    ) -> WorkDayDetailsBuilder<state::SetWorkType<S>>
+    where
+        S::WorkType: state::IsUnset,
+    {
+        self.work_type(work_type)
     }
 
-    pub fn set_start_time(mut self, start_time: DateTime<Utc>) -> Self {
-        self.start_time = start_time;
-        self
+    #[deprecated(note = "use `start_time`")]
+    pub fn set_start_time(
+        self,
+        start_time: impl Into<AthensTime>,
+    ) -> WorkDayDetailsBuilder<state::SetStartTime<S>>
+    where
+        S::StartTime: state::IsUnset,
+    {
+        self.start_time(start_time)
     }
 
-    pub fn set_end_time(mut self, end_time: DateTime<Utc>) -> Self {
-        self.end_time = end_time;
-        self
+    #[deprecated(note = "use `end_time`")]
+    pub fn set_end_time(
+        self,
+        end_time: impl Into<AthensTime>,
+    ) -> WorkDayDetailsBuilder<state::SetEndTime<S>>
+    where
+        S::EndTime: state::IsUnset,
+    {
+        self.end_time(end_time)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chrono::{DateTime, NaiveTime, Utc};
 
     #[test]
-    fn test_serialize_work_day_details_build_fails_when_work_type_not_set() {
+    fn test_serialize_work_day_details_build_is_ok() {
         let start_time = "2014-11-28T12:00:00Z";
         let end_time = "2014-11-28T16:00:00Z";
         let work_day_details = WorkDayDetailsBuilder::builder()
-            .set_start_time(start_time.parse::<DateTime<Utc>>().unwrap())
-            .set_end_time(end_time.parse::<DateTime<Utc>>().unwrap())
+            .work_type(ScheduleWorkType::WorkFromHome)
+            .start_time(start_time.parse::<DateTime<Utc>>().unwrap())
+            .end_time(end_time.parse::<DateTime<Utc>>().unwrap())
             .build();
 
-        assert!(work_day_details.is_err());
+        assert_eq!(work_day_details.work_type.value(), "ΤΗΛ");
         assert_eq!(
-            work_day_details.err().unwrap().to_string(),
-            "Work type is required"
+            work_day_details.end_time,
+            AthensTime::from(end_time.parse::<DateTime<Utc>>().unwrap())
         );
     }
 
     #[test]
-    fn test_serialize_work_day_details_build_is_ok() {
-        let start_time = "2014-11-28T12:00:00Z";
-        let end_time = "2014-11-28T16:00:00Z";
+    fn test_build_work_day_details_with_times_in_greece() {
         let work_day_details = WorkDayDetailsBuilder::builder()
-            .set_work_type(ScheduleWorkType::WorkFromHome)
-            .set_start_time(start_time.parse::<DateTime<Utc>>().unwrap())
-            .set_end_time(end_time.parse::<DateTime<Utc>>().unwrap())
+            .work_type(ScheduleWorkType::WorkFromHome)
+            .start_time(NaiveTime::from_hms_opt(9, 0, 0).unwrap())
+            .end_time(NaiveTime::from_hms_opt(17, 0, 0).unwrap())
             .build();
 
-        assert!(work_day_details.is_ok());
+        assert_eq!(
+            serde_json::to_string(&work_day_details).unwrap(),
+            r#"{"f_type":"ΤΗΛ","f_from":"09:00","f_to":"17:00"}"#
+        );
     }
 }
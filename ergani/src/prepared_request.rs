@@ -0,0 +1,57 @@
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use serde_json::Value;
+
+use crate::transport::TransportRequest;
+
+/// The value shown instead of the access token in a [PreparedRequest]
+pub const REDACTED_TOKEN: &str = "***";
+
+/// A request to the Ergani API as it would be sent, built without sending anything
+/// * - `method` - The HTTP method of the request
+/// * - `url` - The full URL of the request
+/// * - `headers` - The headers of the request, with the access token replaced by `***`
+/// * - `body` - The serialized JSON envelope of the request
+///
+/// The default transport also sends its `User-Agent` header, which is not part of the prepared request.
+#[derive(Debug, Clone)]
+pub struct PreparedRequest {
+    method: Method,
+    url: String,
+    headers: HeaderMap,
+    body: Value,
+}
+
+impl PreparedRequest {
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn body(&self) -> &Value {
+        &self.body
+    }
+
+    /// Returns the body as pretty-printed JSON, e.g. to keep it as a golden file
+    pub fn body_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.body).unwrap_or_default()
+    }
+}
+
+impl From<TransportRequest> for PreparedRequest {
+    fn from(request: TransportRequest) -> Self {
+        PreparedRequest {
+            method: request.method,
+            url: request.url,
+            headers: request.headers,
+            body: request.body.unwrap_or(Value::Null),
+        }
+    }
+}
@@ -0,0 +1,270 @@
+use std::time::Duration;
+
+use bon::Builder;
+use chrono::{DateTime, Utc};
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::{Method, StatusCode};
+
+/// The reason a single attempt of a request to the Ergani API failed
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum AttemptFailure {
+    /// The connection could not be established, so the request was never sent
+    Connect,
+    /// The request timed out
+    Timeout,
+    /// The request failed for another reason after it may have been sent
+    Other,
+    /// The Ergani API responded with an error status
+    /// * - `status` - The status of the response
+    /// * - `retry_after` - The delay requested by a `Retry-After` header, if any
+    Status(StatusCode, Option<Duration>),
+}
+
+/// Controls how requests to the Ergani API are retried
+/// * - `max_attempts` - The maximum number of attempts per request, including the first one. Defaults to 3.
+/// * - `initial_backoff` - The delay before the first retry. Defaults to 500 milliseconds.
+/// * - `max_backoff` - The upper bound of the computed delay between attempts. Defaults to 30 seconds.
+/// * - `jitter` - Whether delays are randomized to spread retries of concurrent requests. Defaults to `true`.
+/// * - `respect_retry_after` - Whether a `Retry-After` header overrides the computed delay. Defaults to `true`.
+///
+/// Fetches are retried on connection failures, timeouts, `429 Too Many Requests` and `5xx` responses.
+/// Submissions are only retried when the connection could not be established, since any other failure
+/// may mean that Ergani already received the document. A request asked by `Retry-After` to wait longer than
+/// `max_backoff` is not retried, since retrying any sooner would be refused again.
+#[derive(Builder, Clone, Debug)]
+pub struct RetryPolicy {
+    #[builder(default = 3)]
+    max_attempts: u32,
+    #[builder(default = Duration::from_millis(500))]
+    initial_backoff: Duration,
+    #[builder(default = Duration::from_secs(30))]
+    max_backoff: Duration,
+    #[builder(default = true)]
+    jitter: bool,
+    #[builder(default = true)]
+    respect_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that sends every request exactly once
+    pub fn none() -> Self {
+        RetryPolicy::builder().max_attempts(1).build()
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns how long to wait before retrying a failed attempt, or `None` if it should not be retried
+    ///
+    /// # Arguments
+    /// * - `method` - The HTTP method of the request. `GET` requests are fetches, anything else is a submission.
+    /// * - `attempt` - The number of the attempt that failed, starting from 1
+    /// * - `failure` - The reason the attempt failed
+    pub(crate) fn retry_delay(
+        &self,
+        method: &Method,
+        attempt: u32,
+        failure: &AttemptFailure,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !is_retryable(method, failure) {
+            return None;
+        }
+
+        let retry_after = match failure {
+            AttemptFailure::Status(_, retry_after) if self.respect_retry_after => *retry_after,
+            _ => None,
+        };
+
+        match retry_after {
+            Some(retry_after) if retry_after > self.max_backoff => None,
+            Some(retry_after) => Some(retry_after),
+            None => Some(self.backoff(attempt)),
+        }
+    }
+
+    /// Returns the exponential backoff after the given failed attempt, with jitter if enabled
+    ///
+    /// # Arguments
+    /// * - `attempt` - The number of the attempt that failed, starting from 1
+    fn backoff(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(16);
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2u32.pow(exponent))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            backoff.mul_f64(0.5 + fastrand::f64() / 2.0)
+        } else {
+            backoff
+        }
+    }
+}
+
+/// Returns whether a failed attempt of a request with the given method may be retried
+fn is_retryable(method: &Method, failure: &AttemptFailure) -> bool {
+    if method != Method::GET {
+        return *failure == AttemptFailure::Connect;
+    }
+
+    match failure {
+        AttemptFailure::Connect | AttemptFailure::Timeout | AttemptFailure::Other => true,
+        AttemptFailure::Status(status, _) => {
+            *status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+        }
+    }
+}
+
+/// Parses the `Retry-After` header, given either in seconds or as an HTTP date
+///
+/// # Arguments
+/// * - `headers` - The headers of the response
+pub(crate) fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let retry_at = DateTime::parse_from_rfc2822(value)
+        .ok()?
+        .with_timezone(&Utc);
+
+    Some((retry_at - Utc::now()).to_std().unwrap_or(Duration::ZERO))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    fn policy_without_jitter() -> RetryPolicy {
+        RetryPolicy::builder()
+            .max_attempts(5)
+            .initial_backoff(Duration::from_secs(1))
+            .max_backoff(Duration::from_secs(5))
+            .jitter(false)
+            .build()
+    }
+
+    #[test]
+    fn test_backoff_grows_exponentially_up_to_max_backoff() {
+        let policy = policy_without_jitter();
+        let failure = AttemptFailure::Status(StatusCode::SERVICE_UNAVAILABLE, None);
+
+        let delays: Vec<Option<Duration>> = (1..=5)
+            .map(|attempt| policy.retry_delay(&Method::GET, attempt, &failure))
+            .collect();
+
+        assert_eq!(
+            delays,
+            vec![
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(5)),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_jitter_keeps_backoff_within_bounds() {
+        let policy = RetryPolicy::builder()
+            .initial_backoff(Duration::from_secs(2))
+            .build();
+
+        for _ in 0..100 {
+            let delay = policy
+                .retry_delay(&Method::GET, 1, &AttemptFailure::Timeout)
+                .unwrap();
+            assert!(delay >= Duration::from_secs(1) && delay <= Duration::from_secs(2));
+        }
+    }
+
+    #[test]
+    fn test_retry_after_overrides_backoff() {
+        let policy = policy_without_jitter();
+        let failure =
+            AttemptFailure::Status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(3)));
+
+        let delay = policy.retry_delay(&Method::GET, 1, &failure);
+
+        assert_eq!(delay, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn test_retry_after_beyond_max_backoff_is_not_retried() {
+        let policy = policy_without_jitter();
+        let failure =
+            AttemptFailure::Status(StatusCode::TOO_MANY_REQUESTS, Some(Duration::from_secs(60)));
+
+        assert_eq!(policy.retry_delay(&Method::GET, 1, &failure), None);
+    }
+
+    #[test]
+    fn test_fetch_is_not_retried_on_client_error() {
+        let policy = policy_without_jitter();
+        let failure = AttemptFailure::Status(StatusCode::BAD_REQUEST, None);
+
+        assert_eq!(policy.retry_delay(&Method::GET, 1, &failure), None);
+    }
+
+    #[test]
+    fn test_submission_is_only_retried_on_connect_failure() {
+        let policy = policy_without_jitter();
+
+        assert!(policy
+            .retry_delay(&Method::POST, 1, &AttemptFailure::Connect)
+            .is_some());
+        assert!(policy
+            .retry_delay(&Method::POST, 1, &AttemptFailure::Timeout)
+            .is_none());
+        assert!(policy
+            .retry_delay(&Method::POST, 1, &AttemptFailure::Other)
+            .is_none());
+        assert!(policy
+            .retry_delay(
+                &Method::POST,
+                1,
+                &AttemptFailure::Status(StatusCode::SERVICE_UNAVAILABLE, None)
+            )
+            .is_none());
+    }
+
+    #[test]
+    fn test_no_retries_policy() {
+        let policy = RetryPolicy::none();
+
+        assert_eq!(
+            policy.retry_delay(&Method::GET, 1, &AttemptFailure::Connect),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_parse_retry_after_http_date_in_the_past() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt::{Debug, Display, Formatter};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize;
+
+/// A string holding a secret, such as a password or a token
+///
+/// The secret is zeroized when it is dropped and prints as `***` in Debug and Display output, so it does
+/// not end up in logs by mistake. It can only be read through [SecretString::expose_secret]. Serializing
+/// it writes the secret in plain text, e.g. when a [TokenStore](crate::auth::token_store::TokenStore)
+/// persists the authentication state.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct SecretString(String);
+
+impl SecretString {
+    /// Creates a secret from the given value
+    ///
+    /// # Arguments
+    /// * - `secret` - The value to keep secret
+    pub fn new(secret: impl Into<String>) -> Self {
+        SecretString(secret.into())
+    }
+
+    /// Returns the secret value
+    pub fn expose_secret(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(secret: String) -> Self {
+        SecretString(secret)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(secret: &str) -> Self {
+        SecretString(secret.to_string())
+    }
+}
+
+impl Drop for SecretString {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl Debug for SecretString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "***")
+    }
+}
+
+impl Display for SecretString {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "***")
+    }
+}
+
+impl Serialize for SecretString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SecretString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(SecretString)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_string_is_redacted_in_output() {
+        let secret = SecretString::new("password");
+
+        assert_eq!(format!("{secret}"), "***");
+        assert_eq!(format!("{secret:?}"), "***");
+        assert_eq!(secret.expose_secret(), "password");
+    }
+
+    #[test]
+    fn test_secret_string_serializes_as_plain_string() {
+        let secret: SecretString = serde_json::from_str(r#""token""#).unwrap();
+
+        assert_eq!(secret.expose_secret(), "token");
+        assert_eq!(serde_json::to_string(&secret).unwrap(), r#""token""#);
+    }
+}
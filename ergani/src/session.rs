@@ -0,0 +1,654 @@
+use std::sync::{Arc, Weak};
+
+use async_trait::async_trait;
+use bon::bon;
+use tokio::sync::{Mutex, RwLock};
+use tracing::{info, warn};
+
+use crate::api_error::ErganiClientError;
+use crate::auth::authenticator::{ErganiAuthenticationState, ErganiAuthenticator};
+use crate::auth::login_payload::LoginPayload;
+use crate::auth::token_store::TokenStore;
+use crate::client::{AuthStateRenewal, ErganiClient, SubmissionResponse};
+use crate::ergani_document::ErganiDocument;
+use crate::ergani_fetch_response::ErganiFetchResponse;
+use crate::models::company::company_daily_schedule::CompanyDailySchedule;
+use crate::models::company::company_overtime::CompanyOvertime;
+use crate::models::company::company_weekly_schedule::CompanyWeeklySchedule;
+use crate::models::company::company_work_card::CompanyWorkCard;
+use crate::models::types::afm::Afm;
+use crate::responses::day_schedule_response::DayScheduleResponseRoot;
+use crate::responses::lookup_response::LookupRoot;
+use crate::responses::overtime_response::OvertimeResponseRoot;
+use crate::responses::week_schedule_response::WeekScheduleResponseRoot;
+use crate::responses::work_card_response::WorkCardResponseRoot;
+use crate::submission_outcome::SubmissionOutcome;
+
+/// A session for the Ergani API that owns the authentication state
+///
+/// The session refreshes the access token before it expires and keeps the latest
+/// authentication state internally, so callers do not have to thread it through every call.
+/// Clones share the same state, and concurrent callers wait on a single refresh instead of
+/// each refreshing the token on their own, whether the token is about to expire or was rejected.
+#[derive(Clone)]
+pub struct ErganiSession {
+    inner: Arc<ErganiSessionInner>,
+}
+
+struct ErganiSessionInner {
+    authenticator: ErganiAuthenticator,
+    client: ErganiClient,
+    auth_state: RwLock<ErganiAuthenticationState>,
+    refresh_lock: Mutex<()>,
+    token_store: Option<Arc<dyn TokenStore>>,
+}
+
+#[bon]
+impl ErganiSession {
+    /// Creates a new session from an existing authentication state
+    ///
+    /// # Arguments
+    /// * - `authenticator` - The authenticator used to refresh the authentication state
+    /// * - `client` - The client used to communicate with the Ergani API
+    /// * - `auth_state` - The initial authentication state of the session
+    /// * - `token_store` - Where every refreshed authentication state is saved, and cleared from once the refresh token expires
+    #[builder]
+    pub fn new(
+        authenticator: ErganiAuthenticator,
+        client: ErganiClient,
+        auth_state: ErganiAuthenticationState,
+        token_store: Option<Arc<dyn TokenStore>>,
+    ) -> Self {
+        ErganiSession {
+            inner: Arc::new_cyclic(|session| ErganiSessionInner {
+                authenticator,
+                client: client.with_auth_state_renewal(Arc::new(SessionAuthStateRenewal {
+                    session: session.clone(),
+                    client: client.clone(),
+                })),
+                auth_state: RwLock::new(auth_state),
+                refresh_lock: Mutex::new(()),
+                token_store,
+            }),
+        }
+    }
+
+    /// Logs in to the Ergani API and creates a new session from the resulting authentication state
+    ///
+    /// # Arguments
+    /// * - `authenticator` - The authenticator used to log in and refresh the authentication state
+    /// * - `client` - The client used to communicate with the Ergani API
+    /// * - `login_payload` - The credentials used to log in
+    ///
+    /// # Returns
+    /// * - `ErganiSession` - A session holding the authentication state of the login
+    pub async fn login(
+        authenticator: ErganiAuthenticator,
+        client: ErganiClient,
+        login_payload: LoginPayload,
+    ) -> Result<Self, ErganiClientError> {
+        let auth_state = authenticator.login(login_payload).await?;
+
+        Ok(ErganiSession::builder()
+            .authenticator(authenticator)
+            .client(client)
+            .auth_state(auth_state)
+            .build())
+    }
+
+    /// Creates a session from the authentication state in the token store, logging in only if there is
+    /// no stored state or its refresh token has expired
+    ///
+    /// # Arguments
+    /// * - `authenticator` - The authenticator used to log in and refresh the authentication state
+    /// * - `client` - The client used to communicate with the Ergani API
+    /// * - `token_store` - The store the authentication state is loaded from and saved to
+    /// * - `login_payload` - The credentials used to log in if needed
+    ///
+    /// # Returns
+    /// * - `ErganiSession` - A session holding the stored or newly logged in authentication state
+    pub async fn restore_or_login(
+        authenticator: ErganiAuthenticator,
+        client: ErganiClient,
+        token_store: Arc<dyn TokenStore>,
+        login_payload: LoginPayload,
+    ) -> Result<Self, ErganiClientError> {
+        let stored_auth_state = token_store
+            .load()
+            .await?
+            .filter(|auth_state| !auth_state.is_refresh_token_expired_at(client.server_now()));
+
+        let auth_state = match stored_auth_state {
+            Some(auth_state) => {
+                info!("Restored session authentication state from the token store");
+                auth_state
+            }
+            None => {
+                let auth_state = authenticator.login(login_payload).await?;
+                token_store.save(&auth_state).await?;
+                auth_state
+            }
+        };
+
+        Ok(ErganiSession::builder()
+            .authenticator(authenticator)
+            .client(client)
+            .auth_state(auth_state)
+            .token_store(token_store)
+            .build())
+    }
+
+    /// Returns the current authentication state of the session
+    pub async fn auth_state(&self) -> ErganiAuthenticationState {
+        self.inner.auth_state.read().await.clone()
+    }
+
+    /// Refreshes the authentication state of the session
+    ///
+    /// If another task refreshed the state while this call was waiting, that state is
+    /// returned instead of refreshing again.
+    ///
+    /// # Returns
+    /// * - `ErganiAuthenticationState` - The refreshed authentication state
+    pub async fn refresh(&self) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        let stale_auth_state = self.auth_state().await;
+
+        self._refresh(&stale_auth_state).await
+    }
+
+    /// Returns the AFM of the employer the session acts for, if it was given at login
+    pub async fn employer_tax_identification_number(&self) -> Option<Afm> {
+        self.auth_state()
+            .await
+            .employer_tax_identification_number()
+            .cloned()
+    }
+
+    /// Submits documents of any [ErganiDocument] type to the Ergani API
+    ///
+    /// See [ErganiClient::submit]
+    pub async fn submit<D: ErganiDocument>(
+        &self,
+        documents: Vec<D>,
+    ) -> Result<Vec<D::Response>, ErganiClientError> {
+        let auth_state = self._valid_auth_state().await?;
+        let outcome = self
+            .inner
+            .client
+            .submit(documents, auth_state.clone())
+            .await?;
+
+        self._unwrap_submission_outcome(&auth_state, outcome).await
+    }
+
+    /// Submits work card records (check-in, check-out) for employees to the Ergani API
+    ///
+    /// See [ErganiClient::submit_work_card]
+    pub async fn submit_work_card(
+        &self,
+        company_work_cards: Vec<CompanyWorkCard>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.submit(company_work_cards).await
+    }
+
+    /// Submits overtime records for employees to the Ergani API
+    ///
+    /// See [ErganiClient::submit_overtime]
+    pub async fn submit_overtime(
+        &self,
+        company_overtimes: Vec<CompanyOvertime>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.submit(company_overtimes).await
+    }
+
+    /// Submits schedule records that are updated on a daily basis for employees to the Ergani API
+    ///
+    /// See [ErganiClient::submit_daily_schedule]
+    pub async fn submit_daily_schedule(
+        &self,
+        company_daily_schedules: Vec<CompanyDailySchedule>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.submit(company_daily_schedules).await
+    }
+
+    /// Submits weekly schedule records for employees to the Ergani API
+    ///
+    /// See [ErganiClient::submit_weekly_schedule]
+    pub async fn submit_weekly_schedule(
+        &self,
+        company_weekly_schedules: Vec<CompanyWeeklySchedule>,
+    ) -> Result<Vec<SubmissionResponse>, ErganiClientError> {
+        self.submit(company_weekly_schedules).await
+    }
+
+    /// Fetches the submissions from the Ergani API
+    ///
+    /// See [ErganiClient::fetch_submissions]
+    pub async fn fetch_submissions(&self) -> Result<Option<LookupRoot>, ErganiClientError> {
+        let auth_state = self._valid_auth_state().await?;
+        let response = self
+            .inner
+            .client
+            .fetch_submissions(auth_state.clone())
+            .await?;
+
+        self._unwrap_fetch_response(&auth_state, response).await
+    }
+
+    /// Fetches the weekly schedule from the Ergani API
+    ///
+    /// See [ErganiClient::fetch_weekly_schedule]
+    pub async fn fetch_weekly_schedule(
+        &self,
+    ) -> Result<Option<WeekScheduleResponseRoot>, ErganiClientError> {
+        let auth_state = self._valid_auth_state().await?;
+        let response = self
+            .inner
+            .client
+            .fetch_weekly_schedule(auth_state.clone())
+            .await?;
+
+        self._unwrap_fetch_response(&auth_state, response).await
+    }
+
+    /// Fetches the daily schedule from the Ergani API
+    ///
+    /// See [ErganiClient::fetch_daily_schedule]
+    pub async fn fetch_daily_schedule(
+        &self,
+    ) -> Result<Option<DayScheduleResponseRoot>, ErganiClientError> {
+        let auth_state = self._valid_auth_state().await?;
+        let response = self
+            .inner
+            .client
+            .fetch_daily_schedule(auth_state.clone())
+            .await?;
+
+        self._unwrap_fetch_response(&auth_state, response).await
+    }
+
+    /// Fetches the work cards from the Ergani API
+    ///
+    /// See [ErganiClient::fetch_work_cards]
+    pub async fn fetch_work_cards(
+        &self,
+    ) -> Result<Option<WorkCardResponseRoot>, ErganiClientError> {
+        let auth_state = self._valid_auth_state().await?;
+        let response = self
+            .inner
+            .client
+            .fetch_work_cards(auth_state.clone())
+            .await?;
+
+        self._unwrap_fetch_response(&auth_state, response).await
+    }
+
+    /// Fetches the overtime records from the Ergani API
+    ///
+    /// See [ErganiClient::fetch_overtimes]
+    pub async fn fetch_overtimes(&self) -> Result<Option<OvertimeResponseRoot>, ErganiClientError> {
+        let auth_state = self._valid_auth_state().await?;
+        let response = self
+            .inner
+            .client
+            .fetch_overtimes(auth_state.clone())
+            .await?;
+
+        self._unwrap_fetch_response(&auth_state, response).await
+    }
+
+    /// Returns an authentication state whose access token is not about to expire,
+    /// refreshing it first if needed
+    async fn _valid_auth_state(&self) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        let auth_state = self.auth_state().await;
+
+        let client = &self.inner.client;
+        if !auth_state.access_token_expires_within(client.refresh_margin(), client.server_now()) {
+            return Ok(auth_state);
+        }
+
+        self._refresh(&auth_state).await
+    }
+
+    /// Refreshes the authentication state, unless another task already replaced `stale_auth_state`
+    ///
+    /// # Arguments
+    /// * - `stale_auth_state` - The authentication state the caller considers outdated
+    async fn _refresh(
+        &self,
+        stale_auth_state: &ErganiAuthenticationState,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        let _refresh_guard = self.inner.refresh_lock.lock().await;
+
+        let current_auth_state = self.auth_state().await;
+        if current_auth_state.access_token() != stale_auth_state.access_token() {
+            return Ok(current_auth_state);
+        }
+
+        info!("Renewing session authentication token");
+
+        let refreshed_auth_state = match self
+            .inner
+            .client
+            ._renew_auth_state(&self.inner.authenticator, &current_auth_state)
+            .await
+        {
+            Err(ErganiClientError::RefreshTokenExpired) => {
+                if let Some(token_store) = &self.inner.token_store {
+                    token_store.clear().await?;
+                }
+
+                return Err(ErganiClientError::RefreshTokenExpired);
+            }
+            result => result?,
+        };
+
+        *self.inner.auth_state.write().await = refreshed_auth_state.clone();
+        self._save_auth_state(&refreshed_auth_state).await;
+
+        Ok(refreshed_auth_state)
+    }
+
+    /// Stores the authentication state returned by the client, if it refreshed the one it was given
+    ///
+    /// # Arguments
+    /// * - `used_auth_state` - The authentication state that was passed to the client
+    /// * - `returned_auth_state` - The authentication state the client returned
+    async fn _store_returned_auth_state(
+        &self,
+        used_auth_state: &ErganiAuthenticationState,
+        returned_auth_state: &ErganiAuthenticationState,
+    ) {
+        if returned_auth_state.access_token() == used_auth_state.access_token() {
+            return;
+        }
+
+        let mut auth_state = self.inner.auth_state.write().await;
+        if auth_state.access_token() == used_auth_state.access_token() {
+            *auth_state = returned_auth_state.clone();
+            drop(auth_state);
+
+            self._save_auth_state(returned_auth_state).await;
+        }
+    }
+
+    /// Saves the authentication state to the token store of the session, if it has one
+    ///
+    /// A failed save is logged instead of failing the call that refreshed the state, since the
+    /// session keeps working with the state it holds in memory.
+    ///
+    /// # Arguments
+    /// * - `auth_state` - The authentication state to save
+    async fn _save_auth_state(&self, auth_state: &ErganiAuthenticationState) {
+        let Some(token_store) = &self.inner.token_store else {
+            return;
+        };
+
+        if let Err(error) = token_store.save(auth_state).await {
+            warn!(%error, "Failed to save the session authentication state");
+        }
+    }
+
+    async fn _unwrap_submission_outcome<T>(
+        &self,
+        used_auth_state: &ErganiAuthenticationState,
+        outcome: SubmissionOutcome<T>,
+    ) -> Result<Vec<T>, ErganiClientError> {
+        let (submissions, returned_auth_state) = outcome.into_parts();
+        self._store_returned_auth_state(used_auth_state, &returned_auth_state)
+            .await;
+
+        Ok(submissions)
+    }
+
+    async fn _unwrap_fetch_response<T>(
+        &self,
+        used_auth_state: &ErganiAuthenticationState,
+        response: ErganiFetchResponse<T>,
+    ) -> Result<Option<T>, ErganiClientError> {
+        self._store_returned_auth_state(used_auth_state, response.auth_state())
+            .await;
+
+        Ok(response.into_response())
+    }
+}
+
+/// Renews the authentication state of the requests of a session through [ErganiSession::_refresh], so that
+/// requests rejected with the same access token share a single refresh
+struct SessionAuthStateRenewal {
+    session: Weak<ErganiSessionInner>,
+    client: ErganiClient,
+}
+
+#[async_trait]
+impl AuthStateRenewal for SessionAuthStateRenewal {
+    async fn renew(
+        &self,
+        stale_auth_state: &ErganiAuthenticationState,
+    ) -> Result<ErganiAuthenticationState, ErganiClientError> {
+        match self.session.upgrade() {
+            Some(inner) => ErganiSession { inner }._refresh(stale_auth_state).await,
+            None => {
+                self.client
+                    ._renew_auth_state(&self.client.authenticator(), stale_auth_state)
+                    .await
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::credential_provider::StaticCredentialProvider;
+    use crate::auth::token_store::InMemoryTokenStore;
+    use crate::endpoint::{AUTHENTICATION_ENDPOINT, AUTHENTICATION_REFRESH_ENDPOINT};
+    use crate::internal::tests::{
+        auth_state, client_with_responses, load_fixture_as_text, MockTransport,
+    };
+    use crate::retry_policy::RetryPolicy;
+    use crate::transport::TransportResponse;
+    use chrono::{TimeDelta, Utc};
+    use reqwest::header::AUTHORIZATION;
+    use reqwest::StatusCode;
+
+    fn login_payload() -> LoginPayload {
+        LoginPayload::builder()
+            .username("username".to_string())
+            .password("password".to_string())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_restore_or_login_uses_stored_state_without_logging_in() {
+        let (client, transport) = client_with_responses(vec![]);
+        let token_store = Arc::new(InMemoryTokenStore::default());
+        token_store
+            .save(
+                &auth_state()
+                    .access_token_expires_at(Utc::now() + TimeDelta::minutes(10))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let session = ErganiSession::restore_or_login(
+            client.authenticator(),
+            client,
+            token_store,
+            login_payload(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            session.auth_state().await.access_token().expose_secret(),
+            "access-token"
+        );
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_refresh_saves_refreshed_state_to_token_store() {
+        let (client, _) = client_with_responses(vec![TransportResponse::new(
+            StatusCode::OK,
+            load_fixture_as_text("authentication_response_fixture.json"),
+        )]);
+        let token_store = Arc::new(InMemoryTokenStore::default());
+        let session = ErganiSession::builder()
+            .authenticator(client.authenticator())
+            .client(client)
+            .auth_state(auth_state().access_token_expires_at(Utc::now()).build())
+            .token_store(token_store.clone())
+            .build();
+
+        session.refresh().await.unwrap();
+
+        let stored_auth_state = token_store.load().await.unwrap().unwrap();
+        assert_eq!(
+            stored_auth_state.access_token().expose_secret(),
+            "refreshed-access-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rejected_close_to_expiry_logs_in_again() {
+        let transport = Arc::new(MockTransport::with_responses(vec![
+            TransportResponse::new(StatusCode::UNAUTHORIZED, ""),
+            TransportResponse::new(
+                StatusCode::OK,
+                load_fixture_as_text("authentication_response_fixture.json"),
+            ),
+        ]));
+        let client = ErganiClient::builder()
+            .transport(transport.clone())
+            .retry_policy(RetryPolicy::none())
+            .credential_provider(Arc::new(StaticCredentialProvider::new(login_payload())))
+            .build()
+            .unwrap();
+        let session = ErganiSession::builder()
+            .authenticator(client.authenticator())
+            .client(client)
+            .auth_state(
+                auth_state()
+                    .refresh_token_expires_at(Utc::now() + TimeDelta::minutes(1))
+                    .build(),
+            )
+            .build();
+
+        let auth_state = session.refresh().await.unwrap();
+
+        assert_eq!(
+            auth_state.access_token().expose_secret(),
+            "refreshed-access-token"
+        );
+        let requests = transport.requests();
+        assert!(requests[0].url.ends_with(AUTHENTICATION_REFRESH_ENDPOINT));
+        assert!(requests[1].url.ends_with(AUTHENTICATION_ENDPOINT));
+        assert_eq!(requests[1].body.as_ref().unwrap()["Username"], "username");
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_rejected_with_the_same_token_refresh_it_once() {
+        let transport = Arc::new(MockTransport::with_responder(|request| {
+            let authorization = request.headers.get(AUTHORIZATION);
+
+            if request.url.ends_with(AUTHENTICATION_REFRESH_ENDPOINT) {
+                TransportResponse::new(
+                    StatusCode::OK,
+                    load_fixture_as_text("authentication_response_fixture.json"),
+                )
+            } else if authorization.is_some_and(|value| value == "Bearer refreshed-access-token") {
+                TransportResponse::new(StatusCode::NO_CONTENT, "")
+            } else {
+                TransportResponse::new(StatusCode::UNAUTHORIZED, "")
+            }
+        }));
+        let client = ErganiClient::builder()
+            .transport(transport.clone())
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+        let session = ErganiSession::builder()
+            .authenticator(client.authenticator())
+            .client(client)
+            .auth_state(auth_state().build())
+            .build();
+
+        let tasks = (0..8)
+            .map(|_| {
+                let session = session.clone();
+                tokio::spawn(async move { session.fetch_submissions().await })
+            })
+            .collect::<Vec<_>>();
+        for task in tasks {
+            task.await.unwrap().unwrap();
+        }
+
+        let refreshes = transport
+            .requests()
+            .iter()
+            .filter(|request| request.url.ends_with(AUTHENTICATION_REFRESH_ENDPOINT))
+            .count();
+        assert_eq!(refreshes, 1);
+        assert_eq!(
+            session.auth_state().await.access_token().expose_secret(),
+            "refreshed-access-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_refresh_clears_token_store_when_refresh_token_expired() {
+        let (client, transport) = client_with_responses(vec![]);
+        let auth_state = auth_state()
+            .access_token_expires_at(Utc::now())
+            .refresh_token_expires_at(Utc::now() - TimeDelta::minutes(1))
+            .build();
+        let token_store = Arc::new(InMemoryTokenStore::default());
+        token_store.save(&auth_state).await.unwrap();
+        let session = ErganiSession::builder()
+            .authenticator(client.authenticator())
+            .client(client)
+            .auth_state(auth_state)
+            .token_store(token_store.clone())
+            .build();
+
+        let error = session.refresh().await.err().unwrap();
+
+        assert!(matches!(error, ErganiClientError::RefreshTokenExpired));
+        assert!(token_store.load().await.unwrap().is_none());
+        assert!(transport.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_submit_rejects_documents_for_another_employer() {
+        let (client, transport) = client_with_responses(vec![]);
+        let auth_state = auth_state()
+            .employer_tax_identification_number("123456783".parse().unwrap())
+            .build();
+        let session = ErganiSession::builder()
+            .authenticator(client.authenticator())
+            .client(client)
+            .auth_state(auth_state)
+            .build();
+        let company_work_card = CompanyWorkCard {
+            employer_tax_identification_number: "987654324".parse().unwrap(),
+            business_branch_number: 1,
+            comments: None,
+            card_details: vec![],
+        };
+
+        let error = session
+            .submit_work_card(vec![company_work_card])
+            .await
+            .err()
+            .unwrap();
+
+        assert!(matches!(
+            error,
+            ErganiClientError::EmployerMismatch { expected, found }
+                if expected == ["123456783"] && found == "987654324"
+        ));
+        assert!(transport.requests().is_empty());
+    }
+}
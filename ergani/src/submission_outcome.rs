@@ -0,0 +1,35 @@
+use bon::Builder;
+
+use crate::auth::authenticator::ErganiAuthenticationState;
+use crate::client::SubmissionResponse;
+use crate::environment::ErganiEnvironment;
+
+/// The outcome of a submission to the Ergani API
+/// * - `submissions` - The submission receipts returned by the Ergani API
+/// * - `auth_state` - The authentication state after the submission, refreshed if the access token was rejected
+/// * - `environment` - The environment the documents were submitted to
+#[derive(Builder)]
+pub struct SubmissionOutcome<T = SubmissionResponse> {
+    submissions: Vec<T>,
+    auth_state: ErganiAuthenticationState,
+    #[builder(default)]
+    environment: ErganiEnvironment,
+}
+
+impl<T> SubmissionOutcome<T> {
+    pub fn submissions(&self) -> &[T] {
+        &self.submissions
+    }
+
+    pub fn auth_state(&self) -> &ErganiAuthenticationState {
+        &self.auth_state
+    }
+
+    pub fn environment(&self) -> &ErganiEnvironment {
+        &self.environment
+    }
+
+    pub fn into_parts(self) -> (Vec<T>, ErganiAuthenticationState) {
+        (self.submissions, self.auth_state)
+    }
+}
@@ -0,0 +1,207 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use async_trait::async_trait;
+use reqwest::header::{HeaderMap, HeaderValue, InvalidHeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::{Method, StatusCode};
+use serde_json::Value;
+use zeroize::Zeroizing;
+
+/// A request to the Ergani API, as handed to an [ErganiTransport]
+/// * - `method` - The HTTP method of the request
+/// * - `url` - The full URL of the request
+/// * - `headers` - The headers of the request, including the `Authorization` header if authenticated
+/// * - `body` - The JSON body of the request, if any
+#[derive(Debug, Clone)]
+pub struct TransportRequest {
+    pub method: Method,
+    pub url: String,
+    pub headers: HeaderMap,
+    pub body: Option<Value>,
+}
+
+impl TransportRequest {
+    /// Creates a request with a `Content-Type: application/json` header if it has a body
+    ///
+    /// # Arguments
+    /// * - `method` - The HTTP method of the request
+    /// * - `url` - The full URL of the request
+    /// * - `body` - The JSON body of the request, if any
+    pub fn new(method: Method, url: impl Into<String>, body: Option<Value>) -> Self {
+        let mut headers = HeaderMap::new();
+        if body.is_some() {
+            headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        }
+
+        TransportRequest {
+            method,
+            url: url.into(),
+            headers,
+            body,
+        }
+    }
+
+    /// Adds a bearer `Authorization` header with the given access token, marked as sensitive
+    ///
+    /// The header is built in a buffer that is zeroized once the header value is created.
+    ///
+    /// # Arguments
+    /// * - `access_token` - The access token to authenticate the request with
+    ///
+    /// # Errors
+    /// * - `[InvalidHeaderValue]` - Raised if the access token has characters that are not allowed in a header
+    pub fn with_bearer_auth(mut self, access_token: &str) -> Result<Self, InvalidHeaderValue> {
+        let mut authorization = Zeroizing::new(String::with_capacity(7 + access_token.len()));
+        authorization.push_str("Bearer ");
+        authorization.push_str(access_token);
+
+        let mut value = HeaderValue::from_str(&authorization)?;
+        value.set_sensitive(true);
+        self.headers.insert(AUTHORIZATION, value);
+
+        Ok(self)
+    }
+}
+
+/// A response of the Ergani API, as returned by an [ErganiTransport]
+/// * - `status` - The HTTP status of the response
+/// * - `headers` - The headers of the response
+/// * - `body` - The raw body of the response
+#[derive(Debug, Clone)]
+pub struct TransportResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+impl TransportResponse {
+    /// Creates a response without headers
+    ///
+    /// # Arguments
+    /// * - `status` - The HTTP status of the response
+    /// * - `body` - The raw body of the response
+    pub fn new(status: StatusCode, body: impl Into<String>) -> Self {
+        TransportResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.into(),
+        }
+    }
+}
+
+/// The kind of failure of a [TransportError], used to decide whether a request can be retried
+#[non_exhaustive]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransportErrorKind {
+    /// The connection could not be established, so the request was never sent
+    Connect,
+    /// The request timed out
+    Timeout,
+    /// Any other failure, after which the request may have been received
+    Other,
+}
+
+/// An error raised by an [ErganiTransport] when no response could be received
+#[derive(thiserror::Error, Debug)]
+pub struct TransportError {
+    kind: TransportErrorKind,
+    #[source]
+    source: Box<dyn Error + Send + Sync>,
+}
+
+impl TransportError {
+    /// Creates a new transport error
+    ///
+    /// # Arguments
+    /// * - `kind` - The kind of failure
+    /// * - `source` - The underlying error
+    pub fn new(kind: TransportErrorKind, source: impl Into<Box<dyn Error + Send + Sync>>) -> Self {
+        TransportError {
+            kind,
+            source: source.into(),
+        }
+    }
+
+    pub fn kind(&self) -> TransportErrorKind {
+        self.kind
+    }
+}
+
+impl Display for TransportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.source)
+    }
+}
+
+impl From<reqwest::Error> for TransportError {
+    fn from(error: reqwest::Error) -> Self {
+        let kind = if error.is_connect() {
+            TransportErrorKind::Connect
+        } else if error.is_timeout() {
+            TransportErrorKind::Timeout
+        } else {
+            TransportErrorKind::Other
+        };
+
+        TransportError::new(kind, error)
+    }
+}
+
+/// Sends requests to the Ergani API
+///
+/// [ErganiClient](crate::client::ErganiClient) and
+/// [ErganiAuthenticator](crate::auth::authenticator::ErganiAuthenticator) send every request through
+/// a transport. The default one is [ReqwestTransport]; plug in your own, e.g. one that returns canned
+/// responses, to run the client without a network.
+#[async_trait]
+pub trait ErganiTransport: Send + Sync {
+    /// Sends a request and returns the response, whatever its status
+    ///
+    /// # Arguments
+    /// * - `request` - The request to send
+    ///
+    /// # Errors
+    /// * - `TransportError` - Raised if no response could be received
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, TransportError>;
+}
+
+/// The default [ErganiTransport], backed by a [reqwest::Client]
+#[derive(Clone, Default)]
+pub struct ReqwestTransport {
+    http_client: reqwest::Client,
+}
+
+impl ReqwestTransport {
+    /// Creates a transport that sends requests with the given HTTP client
+    ///
+    /// # Arguments
+    /// * - `http_client` - The configured HTTP client
+    pub fn new(http_client: reqwest::Client) -> Self {
+        ReqwestTransport { http_client }
+    }
+}
+
+#[async_trait]
+impl ErganiTransport for ReqwestTransport {
+    async fn send(&self, request: TransportRequest) -> Result<TransportResponse, TransportError> {
+        let mut request_builder = self
+            .http_client
+            .request(request.method, request.url)
+            .headers(request.headers);
+
+        if let Some(body) = request.body {
+            request_builder = request_builder.json(&body);
+        }
+
+        let response = request_builder.send().await?;
+        let status = response.status();
+        let headers = response.headers().clone();
+        let body = response.text().await?;
+
+        Ok(TransportResponse {
+            status,
+            headers,
+            body,
+        })
+    }
+}
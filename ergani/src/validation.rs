@@ -0,0 +1,283 @@
+use std::fmt::{Display, Formatter};
+
+use crate::models::types::athens_time::{AthensTime, AthensTimeError};
+
+/// An issue that would make the Ergani API reject a document
+/// * - `path` - Where the issue is, e.g. `card_details[3].employee_last_name`
+/// * - `message` - What is wrong
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationIssue {
+    path: String,
+    message: String,
+}
+
+impl ValidationIssue {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        ValidationIssue {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// Moves the issue under a parent path, e.g. `[0]` turns `comments` into `[0].comments`
+    pub(crate) fn under(self, parent: &str) -> Self {
+        ValidationIssue {
+            path: join_path(parent, &self.path),
+            message: self.message,
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.message)
+    }
+}
+
+/// Every issue found while validating a document or a batch of documents
+#[derive(thiserror::Error, Clone, Debug, PartialEq, Eq)]
+pub struct ValidationErrors {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationErrors {
+    pub(crate) fn new(issues: Vec<ValidationIssue>) -> Self {
+        ValidationErrors { issues }
+    }
+
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let issues = self
+            .issues
+            .iter()
+            .map(ValidationIssue::to_string)
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", issues.join("; "))
+    }
+}
+
+/// Checks a document for everything the Ergani API would reject, reporting all the issues at once
+///
+/// Implementations add their own issues and those of the values they contain, so that validating a
+/// [CompanyWorkCard](crate::models::company::company_work_card::CompanyWorkCard) also validates every
+/// `WorkCard` of it.
+pub trait Validate {
+    /// Adds every issue of the value to `issues`, with paths relative to `path`
+    ///
+    /// # Arguments
+    /// * - `path` - The path of the value, empty for the value being validated
+    /// * - `issues` - The issues found so far
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>);
+
+    /// Returns every issue of the value
+    fn issues(&self) -> Vec<ValidationIssue> {
+        let mut issues = vec![];
+        self.collect_issues("", &mut issues);
+
+        issues
+    }
+
+    /// Checks the value, failing with every issue found
+    ///
+    /// # Errors
+    /// * - `[ValidationErrors]` - Raised if the value has any issue
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let issues = self.issues();
+
+        match issues.is_empty() {
+            true => Ok(()),
+            false => Err(ValidationErrors::new(issues)),
+        }
+    }
+}
+
+impl<T: Validate> Validate for [T] {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        for (index, value) in self.iter().enumerate() {
+            value.collect_issues(&format!("{path}[{index}]"), issues);
+        }
+    }
+}
+
+impl<T: Validate> Validate for Vec<T> {
+    fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+        self.as_slice().collect_issues(path, issues)
+    }
+}
+
+/// Joins a field or an index to the path of the value holding it
+pub(crate) fn join_path(path: &str, field: &str) -> String {
+    match (path.is_empty(), field.starts_with('[')) {
+        (true, _) => field.to_string(),
+        (false, true) => format!("{path}{field}"),
+        (false, false) => format!("{path}.{field}"),
+    }
+}
+
+/// Adds an issue if a required text field is empty or blank
+pub(crate) fn check_not_blank(
+    path: &str,
+    field: &str,
+    value: &str,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if value.trim().is_empty() {
+        issues.push(ValidationIssue::new(
+            join_path(path, field),
+            "must not be empty",
+        ));
+    }
+}
+
+/// Adds an issue if a code is empty or contains anything but digits
+pub(crate) fn check_numeric_code(
+    path: &str,
+    field: &str,
+    value: &str,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if value.trim().is_empty() {
+        issues.push(ValidationIssue::new(
+            join_path(path, field),
+            "must not be empty",
+        ));
+    } else if !value.chars().all(|character| character.is_ascii_digit()) {
+        issues.push(ValidationIssue::new(
+            join_path(path, field),
+            format!("must contain digits only, found \"{value}\""),
+        ));
+    }
+}
+
+/// Adds an issue if a business branch number is negative
+pub(crate) fn check_business_branch_number(
+    path: &str,
+    business_branch_number: i64,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if business_branch_number < 0 {
+        issues.push(ValidationIssue::new(
+            join_path(path, "business_branch_number"),
+            format!("must not be negative, found {business_branch_number}"),
+        ));
+    }
+}
+
+/// Adds an issue under the end field if a period ends before it starts
+pub(crate) fn check_period<T: PartialOrd + Display>(
+    path: &str,
+    (start_field, start): (&str, &T),
+    (end_field, end): (&str, &T),
+    issues: &mut Vec<ValidationIssue>,
+) {
+    if end < start {
+        issues.push(ValidationIssue::new(
+            join_path(path, end_field),
+            format!("{end} is before {start_field} {start}"),
+        ));
+    }
+}
+
+/// Adds an issue under the field if a time cannot be placed on the clocks in Greece, returning the local time otherwise
+pub(crate) fn check_athens_time<T>(
+    path: &str,
+    field: &str,
+    local: Result<T, AthensTimeError>,
+    issues: &mut Vec<ValidationIssue>,
+) -> Option<T> {
+    match local {
+        Ok(local) => Some(local),
+        Err(error) => {
+            issues.push(ValidationIssue::new(
+                join_path(path, field),
+                error.to_string(),
+            ));
+            None
+        }
+    }
+}
+
+/// Adds an issue under a field whose time cannot be placed on the clocks in Greece, and under the end field if a
+/// period whose times have dates ends before it starts
+pub(crate) fn check_time_period(
+    path: &str,
+    (start_field, start): (&str, &AthensTime),
+    (end_field, end): (&str, &AthensTime),
+    issues: &mut Vec<ValidationIssue>,
+) {
+    let start = check_athens_time(path, start_field, start.local_datetime(), issues).flatten();
+    let end = check_athens_time(path, end_field, end.local_datetime(), issues).flatten();
+
+    if let (Some(start), Some(end)) = (start, end) {
+        check_period(path, (start_field, &start), (end_field, &end), issues);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Row {
+        name: String,
+    }
+
+    impl Validate for Row {
+        fn collect_issues(&self, path: &str, issues: &mut Vec<ValidationIssue>) {
+            check_not_blank(path, "name", &self.name, issues);
+        }
+    }
+
+    #[test]
+    fn test_validate_collects_every_issue_with_its_path() {
+        let rows = vec![
+            Row {
+                name: "".to_string(),
+            },
+            Row {
+                name: "ΓΕΩΡΓΙΟΣ".to_string(),
+            },
+            Row {
+                name: " ".to_string(),
+            },
+        ];
+
+        let errors = rows.validate().unwrap_err();
+
+        assert_eq!(
+            errors.issues(),
+            [
+                ValidationIssue::new("[0].name", "must not be empty"),
+                ValidationIssue::new("[2].name", "must not be empty"),
+            ]
+        );
+        assert_eq!(
+            errors.to_string(),
+            "[0].name: must not be empty; [2].name: must not be empty"
+        );
+        assert!(rows[1..2].validate().is_ok());
+    }
+
+    #[test]
+    fn test_join_path() {
+        assert_eq!(join_path("", "comments"), "comments");
+        assert_eq!(join_path("card_details", "[3]"), "card_details[3]");
+        assert_eq!(
+            join_path("card_details[3]", "employee_last_name"),
+            "card_details[3].employee_last_name"
+        );
+    }
+}
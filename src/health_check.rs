@@ -0,0 +1,51 @@
+use anyhow::{bail, Result};
+use comfy_table::presets::UTF8_FULL;
+use comfy_table::{Attribute, Cell, ContentArrangement, Table};
+use ergani::auth::login_payload::LoginPayload;
+use ergani::client::ErganiClient;
+
+pub(crate) async fn health_check(
+    ergani_client: &ErganiClient,
+    login_payload: LoginPayload,
+) -> Result<()> {
+    let report = ergani_client.health_check(login_payload).await;
+
+    let mut health_check_table = Table::new();
+
+    health_check_table
+        .load_preset(UTF8_FULL)
+        .set_content_arrangement(ContentArrangement::Dynamic)
+        .set_header(vec![
+            Cell::new("Step").add_attribute(Attribute::Bold),
+            Cell::new("Latency").add_attribute(Attribute::Bold),
+            Cell::new("Result").add_attribute(Attribute::Bold),
+        ]);
+
+    for step in report.steps() {
+        health_check_table.add_row(vec![
+            Cell::new(step.step().to_string()),
+            Cell::new(format!("{} ms", step.latency().as_millis())),
+            Cell::new(step.error().unwrap_or("OK")),
+        ]);
+    }
+
+    println!("Environment: {}", report.environment());
+    println!("{health_check_table}");
+
+    if let Some(token_expires_at) = report.token_expires_at() {
+        println!("Access token expires at: {token_expires_at}");
+    }
+
+    if let Some(clock_skew) = report.clock_skew() {
+        println!(
+            "Server clock is {} ms ahead of the local clock",
+            clock_skew.num_milliseconds()
+        );
+    }
+
+    if let Some(failed_step) = report.failed_step() {
+        bail!("Health check failed at the {} step", failed_step.step());
+    }
+
+    Ok(())
+}
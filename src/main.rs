@@ -3,6 +3,7 @@ mod fetch_overtime;
 mod fetch_submission_types;
 mod fetch_weekly_schedule;
 mod fetch_work_cards;
+mod health_check;
 mod submit_daily_schedule;
 mod submit_overtime;
 mod submit_weekly_schedule;
@@ -11,8 +12,8 @@ mod submit_work_card;
 use anyhow::{Error, Result};
 use comfy_table::{Attribute, Cell, CellAlignment, Table};
 use ergani::{
-    auth::{authenticator::ErganiAuthenticator, login_payload::LoginPayload},
-    client::ErganiClient,
+    auth::login_payload::LoginPayload, client::ErganiClient, environment::ErganiEnvironment,
+    models::types::afm::Afm,
 };
 use std::env;
 
@@ -25,21 +26,38 @@ async fn main() -> Result<()> {
 
     let ergani_username = env::var("ERGANI_USERNAME")?;
     let ergani_password = env::var("ERGANI_PASSWORD")?;
-    let ergani_base_url = env::var("ERGANI_BASE_URL")
-        .unwrap_or("https://trialeservices.yeka.gr/WebServicesAPI/api".to_string());
+    let ergani_environment = match env::var("ERGANI_BASE_URL") {
+        Ok(ergani_base_url) => ErganiEnvironment::from_base_url(&ergani_base_url)?,
+        Err(_) => ErganiEnvironment::Trial,
+    };
+    let allow_production = env::var("ERGANI_ALLOW_PRODUCTION").is_ok_and(|value| value == "true");
 
     let login_payload = LoginPayload::builder()
         .username(ergani_username)
         .password(ergani_password)
         .build();
 
-    let ergani_authenticator = ErganiAuthenticator::builder()
-        .base_url(ergani_base_url.to_string())
-        .build();
+    // The employer of the example documents, the client refuses documents of any other employer
+    let employer: Afm = "123456783".parse()?;
+
+    let client = ErganiClient::builder()
+        .environment(ergani_environment)
+        .allow_production(allow_production)
+        .employer_tax_identification_numbers(vec![employer])
+        .build()?;
+
+    // Check the credentials, base URL, TLS setup and clock with `cargo run -- health-check`
+    if env::args().nth(1).as_deref() == Some("health-check") {
+        let result = health_check::health_check(&client, login_payload).await;
 
-    let auth_state = ergani_authenticator.login(login_payload).await?;
+        if let Err(e) = result {
+            pretty_print_error(e)
+        }
+
+        return Ok(());
+    }
 
-    let client = ErganiClient::init(ergani_base_url);
+    let auth_state = client.authenticator().login(login_payload).await?;
 
     // Submit a work card
     // let result = submit_work_card::submit_work_card(&client, auth_state).await;